@@ -67,12 +67,7 @@ fn write_functions_for_data_elements<W: Write>(
             continue;
         }
 
-        // convert keyword to snake case
-        let keyword = data_element
-            .keyword
-            .replace("\u{200b}", "_")
-            .replace("__", "_") // some keywords include multiple zero-width spaces...
-            .to_lowercase();
+        let keyword = data_element.parsed_keyword().to_snake_case();
         writer.write_all(format!("pub fn {}() -> Tag {{\n", keyword).as_bytes())?;
 
         // data_element.tag is in format "(0008,0001)"
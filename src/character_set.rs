@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+
+/// A code element (G0 or G1) of a character set along with the escape
+/// sequence designating it.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CodeElement {
+    /// The ISO registration number of the graphic character set (e.g.
+    /// "ISO-IR 100"), empty for character sets not registered with ISO (e.g.
+    /// "GB18030").
+    pub iso_registration: String,
+
+    /// The escape sequence designating the code element (e.g. "ESC 02/13
+    /// 04/01"), `None` for character sets without code extensions.
+    pub escape_sequence: Option<String>,
+
+    /// The code element the character set is invoked into (e.g. "G1").
+    pub code_element: String,
+
+    /// The standard defining the character set (e.g. "Supplementary set of
+    /// ISO 8859").
+    pub character_set: String,
+}
+
+impl CodeElement {
+    /// Returns the bytes of the escape sequence given in column/row notation
+    /// (e.g. [0x1B, 0x2D, 0x41] for "ESC 02/13 04/01").
+    pub fn escape_bytes(&self) -> Option<Vec<u8>> {
+        let sequence = self.escape_sequence.as_ref()?;
+        sequence
+            .split_whitespace()
+            .map(|part| {
+                if part == "ESC" {
+                    return Some(0x1B);
+                }
+
+                let mut numbers = part.split('/').map(|n| n.parse::<u8>().ok());
+                match (numbers.next(), numbers.next(), numbers.next()) {
+                    (Some(Some(column)), Some(Some(row)), None) if column < 16 && row < 16 => {
+                        Some(column << 4 | row)
+                    }
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// A defined term of Specific Character Set (0008,0005) as defined in
+/// section C.12.1.1.2 of part 3.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CharacterSet {
+    /// The defined term (e.g. "ISO_IR 100" or "ISO 2022 IR 87"), empty for
+    /// the default repertoire which has no defined term.
+    pub defined_term: String,
+
+    /// The description of the character set (e.g. "Latin alphabet No. 1").
+    pub description: String,
+
+    /// Whether the character set supports code extensions as defined in ISO
+    /// 2022 (i.e. switching character sets with escape sequences).
+    pub code_extensions: bool,
+
+    /// Whether characters may be encoded with more than one byte.
+    pub multi_byte: bool,
+
+    /// The code elements of the character set in the order of the standard.
+    pub elements: Vec<CodeElement>,
+}
+
+impl CharacterSet {
+    /// Returns the IANA name of the encoding to decode values of this
+    /// character set with (e.g. "ISO-8859-1" for "ISO_IR 100").
+    pub fn encoding_name(&self) -> Option<&'static str> {
+        let term = self.defined_term.as_str();
+        let number = term
+            .trim_start_matches("ISO_IR")
+            .trim_start_matches("ISO 2022 IR")
+            .trim();
+        let name = match number {
+            "" | "6" => "US-ASCII",
+            "100" => "ISO-8859-1",
+            "101" => "ISO-8859-2",
+            "109" => "ISO-8859-3",
+            "110" => "ISO-8859-4",
+            "144" => "ISO-8859-5",
+            "127" => "ISO-8859-6",
+            "126" => "ISO-8859-7",
+            "138" => "ISO-8859-8",
+            "148" => "ISO-8859-9",
+            "203" => "ISO-8859-15",
+            "13" => "Shift_JIS",
+            "166" => "TIS-620",
+            "87" => "ISO-2022-JP",
+            "159" => "ISO-2022-JP-2",
+            "149" => "EUC-KR",
+            "58" => "GB2312",
+            "192" => "UTF-8",
+            "GB18030" => "GB18030",
+            "GBK" => "GBK",
+            _ => return None,
+        };
+        Some(name)
+    }
+}
+
+/// All defined terms of Specific Character Set keyed by the defined term.
+#[derive(Clone, Debug, Default)]
+pub struct CharacterSetRegistry {
+    character_sets: BTreeMap<String, CharacterSet>,
+}
+
+impl CharacterSetRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new `CharacterSetRegistry` containing `character_sets`.
+    pub fn from_character_sets<I>(character_sets: I) -> Self
+    where
+        I: IntoIterator<Item = CharacterSet>,
+    {
+        CharacterSetRegistry {
+            character_sets: character_sets
+                .into_iter()
+                .map(|set| (set.defined_term.clone(), set))
+                .collect(),
+        }
+    }
+
+    /// Returns the character set with the given `defined_term` (e.g.
+    /// "ISO_IR 192"), "" for the default repertoire.
+    pub fn get(&self, defined_term: &str) -> Option<&CharacterSet> {
+        self.character_sets.get(defined_term.trim())
+    }
+
+    /// Returns the character sets of all values of a Specific Character Set
+    /// attribute (e.g. "\ISO 2022 IR 87" for the default repertoire and
+    /// Japanese), or `None` if any of the values is unknown.
+    pub fn for_attribute_value(&self, value: &str) -> Option<Vec<&CharacterSet>> {
+        value.split('\\').map(|term| self.get(term)).collect()
+    }
+
+    /// Returns the character set and code element designated by the
+    /// `escape` sequence (e.g. [0x1B, 0x24, 0x42] for "ISO 2022 IR 87").
+    pub fn by_escape_sequence(&self, escape: &[u8]) -> Option<(&CharacterSet, &CodeElement)> {
+        self.character_sets.values().find_map(|set| {
+            set.elements
+                .iter()
+                .find(|element| element.escape_bytes().as_deref() == Some(escape))
+                .map(|element| (set, element))
+        })
+    }
+
+    /// Returns all character sets ordered by defined term.
+    pub fn character_sets(&self) -> impl Iterator<Item = &CharacterSet> {
+        self.character_sets.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.character_sets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.character_sets.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_bytes_converts_column_row_notation() {
+        let element = CodeElement {
+            escape_sequence: Some("ESC 02/04 02/09 04/01".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(element.escape_bytes(), Some(vec![0x1B, 0x24, 0x29, 0x41]));
+        assert_eq!(CodeElement::default().escape_bytes(), None);
+
+        let invalid = CodeElement {
+            escape_sequence: Some("ESC 02/16".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(invalid.escape_bytes(), None);
+    }
+}
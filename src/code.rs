@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use Kind;
+use UID;
+
+/// A coded concept, i.e. a code of a coding scheme along with its meaning.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CodedConcept {
+    /// The designator of the coding scheme (e.g. "DCM" or "SCT").
+    pub scheme_designator: String,
+
+    /// The code within the coding scheme (e.g. "126000").
+    pub code_value: String,
+
+    /// The meaning of the code (e.g. "Imaging Measurement Report").
+    pub code_meaning: String,
+}
+
+impl fmt::Display for CodedConcept {
+    /// Formats the concept as "(126000, DCM, "Imaging Measurement Report")"
+    /// as used throughout the standard.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "({}, {}, \"{}\")",
+            self.code_value, self.scheme_designator, self.code_meaning
+        )
+    }
+}
+
+impl FromStr for CodedConcept {
+    type Err = Box<dyn Error>;
+
+    /// Parses a concept in the format "(126000, DCM, "Imaging Measurement
+    /// Report")", optionally preceded by "EV" or "DT" as used in templates.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || From::from(format!("Invalid coded concept '{}'", s));
+        let inner = s
+            .trim()
+            .trim_start_matches("EV")
+            .trim_start_matches("DT")
+            .trim()
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(error)?;
+
+        let mut parts = inner.splitn(3, ',');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(value), Some(scheme), Some(meaning)) => Ok(CodedConcept {
+                scheme_designator: scheme.trim().to_owned(),
+                code_value: value.trim().to_owned(),
+                code_meaning: meaning.trim().trim_matches('"').to_owned(),
+            }),
+            _ => Err(error()),
+        }
+    }
+}
+
+/// A code defined by a coding scheme along with its definition (e.g. the
+/// codes of the "DICOM Controlled Terminology" in Annex D of part 16).
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DefinedCode {
+    /// The code with its coding scheme designator and meaning.
+    pub concept: CodedConcept,
+
+    /// The definition of the code.
+    pub definition: String,
+
+    /// Additional notes (e.g. "Retired"), empty if there are none.
+    pub notes: String,
+}
+
+/// All codes of a single coding scheme keyed by their code value.
+#[derive(Clone, Debug, Default)]
+pub struct CodeRegistry {
+    scheme_designator: String,
+    scheme_uid: Option<String>,
+    codes: BTreeMap<String, DefinedCode>,
+}
+
+impl CodeRegistry {
+    /// Creates a new empty `CodeRegistry` for the coding scheme with the
+    /// given `scheme_designator` (e.g. "DCM").
+    pub fn new(scheme_designator: &str) -> Self {
+        CodeRegistry {
+            scheme_designator: scheme_designator.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds `code` to the registry, replacing any code with the same value.
+    pub fn insert(&mut self, code: DefinedCode) -> Option<DefinedCode> {
+        self.codes.insert(code.concept.code_value.clone(), code)
+    }
+
+    /// Returns the designator of the coding scheme (e.g. "DCM").
+    pub fn scheme_designator(&self) -> &str {
+        &self.scheme_designator
+    }
+
+    /// Returns the UID of the coding scheme, once linked with
+    /// `link_scheme_uid`.
+    pub fn scheme_uid(&self) -> Option<&str> {
+        self.scheme_uid.as_deref()
+    }
+
+    /// Links the registry to the UID of the coding scheme named `scheme_name`
+    /// (e.g. "DICOM Controlled Terminology") in the UID registry `uids` of
+    /// part 6. Returns whether the UID was found.
+    pub fn link_scheme_uid(&mut self, uids: &[UID], scheme_name: &str) -> bool {
+        self.scheme_uid = uids
+            .iter()
+            .find(|uid| uid.kind == Kind::CodingScheme && uid.normalized_name == scheme_name)
+            .map(|uid| uid.value.clone());
+        self.scheme_uid.is_some()
+    }
+
+    /// Returns the code with the given `code_value` (e.g. "126000").
+    pub fn get(&self, code_value: &str) -> Option<&DefinedCode> {
+        self.codes.get(code_value)
+    }
+
+    /// Returns all codes with the given `code_meaning`, which is not
+    /// necessarily unique within a coding scheme.
+    pub fn find_by_meaning(&self, code_meaning: &str) -> Vec<&DefinedCode> {
+        self.codes
+            .values()
+            .filter(|code| code.concept.code_meaning == code_meaning)
+            .collect()
+    }
+
+    /// Returns whether `concept` is a code of this coding scheme with the
+    /// defined meaning.
+    pub fn is_valid(&self, concept: &CodedConcept) -> bool {
+        concept.scheme_designator == self.scheme_designator
+            && self
+                .get(&concept.code_value)
+                .map(|code| &code.concept.code_meaning)
+                == Some(&concept.code_meaning)
+    }
+
+    /// Returns all codes ordered by code value.
+    pub fn codes(&self) -> impl Iterator<Item = &DefinedCode> {
+        self.codes.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+}
@@ -0,0 +1,550 @@
+use std::error::Error;
+use std::path::Path;
+
+use code::{CodeRegistry, CodedConcept, DefinedCode};
+use context_group::{ContextGroup, ContextGroupMember, ContextGroupRegistry};
+use document::StandardDocument;
+use table::{self, Table};
+use template::{Template, TemplateItem, TemplateRegistry};
+
+use xmltree;
+
+/// A parser for the coded terminology defined in part 16 "Content Mapping
+/// Resource" of the DICOM standard.
+pub struct ContentMappingParser {
+    /// The DICOM standard part 16 xml file once read.
+    document: StandardDocument,
+}
+
+impl ContentMappingParser {
+    /// Creates a new `ContentMappingParser` instance with a downloaded version
+    /// of the current part 16 of the DICOM standard.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Downloading part16.xml fails
+    /// * Reading the downloaded part16.xml fails
+    pub fn new() -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            document: StandardDocument::new(16)?,
+        })
+    }
+
+    /// Creates a new `ContentMappingParser` instance using the part16.xml
+    /// given as `file_path`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Opening the file at `file_path` fails
+    /// * Reading the file at `file_path` fails
+    pub fn with_part16_file(file_path: &Path) -> Result<Self, ::std::io::Error> {
+        Ok(Self {
+            document: StandardDocument::with_file(16, file_path)?,
+        })
+    }
+
+    /// Creates a new `ContentMappingParser` instance given the full
+    /// `contents` of a part16.xml file.
+    pub fn with_part16_file_contents(contents: String) -> Self {
+        Self {
+            document: StandardDocument::with_file_contents(16, contents),
+        }
+    }
+
+    /// Returns the underlying part 16 document.
+    pub fn document(&self) -> &StandardDocument {
+        &self.document
+    }
+
+    /// Returns all context groups, i.e. the contents of all sections labelled
+    /// "CID n".
+    ///
+    /// Keyword, UID, type and version are read from the list of properties at
+    /// the start of each section ("Type:", "Version:", ...), or from a
+    /// paragraph like "Type: Extensible Version: 20030108" in older editions.
+    /// The members are read from the first table of the section, "Include"
+    /// rows are kept as references to the included context group.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if parsing of the part16.xml fails.
+    pub fn parse_context_group_registry(&self) -> Result<ContextGroupRegistry, Box<dyn Error>> {
+        let root = self.document.root()?;
+        let mut sections = Vec::new();
+        collect_sections(root, "CID ", &mut sections);
+
+        let groups = sections
+            .into_iter()
+            .filter_map(|(number, section)| {
+                number
+                    .parse()
+                    .ok()
+                    .map(|cid| Self::context_group(cid, section))
+            })
+            .collect::<Vec<_>>();
+        Ok(ContextGroupRegistry::from_groups(groups))
+    }
+
+    /// Returns all codes of the "DICOM Controlled Terminology" (coding scheme
+    /// designator "DCM") defined in Annex D.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Parsing of the part16.xml fails
+    ///   * The "DICOM Controlled Terminology Definitions" table cannot be
+    ///     found
+    ///   * The table has no "Code Value" or "Code Meaning" column
+    pub fn parse_dcm_code_registry(&self) -> Result<CodeRegistry, Box<dyn Error>> {
+        let caption = "DICOM Controlled Terminology Definitions";
+        let id = match self
+            .document
+            .tables()?
+            .into_iter()
+            .find(|t| t.caption.starts_with(caption))
+        {
+            Some(info) => info.id,
+            None => return Err(From::from(format!("Unable to find table '{}'.", caption))),
+        };
+
+        let table = self.document.table(&id)?;
+        let (value_column, meaning_column) =
+            match (table.column("Code Value"), table.column("Code Meaning")) {
+                (Some(value), Some(meaning)) => (value, meaning),
+                _ => {
+                    return Err(From::from(format!(
+                        "Unable to find columns 'Code Value' and 'Code Meaning' in table '{}'.",
+                        caption
+                    )))
+                }
+            };
+        let definition_column = table.column("Definition");
+        let notes_column = table.column("Notes");
+
+        let mut registry = CodeRegistry::new("DCM");
+        for row in &table.rows {
+            let text = |column: Option<usize>| {
+                column
+                    .and_then(|c| row.get(c))
+                    .map(|cell| cell.text.clone())
+                    .unwrap_or_default()
+            };
+            registry.insert(DefinedCode {
+                concept: CodedConcept {
+                    scheme_designator: "DCM".to_owned(),
+                    code_value: text(Some(value_column)),
+                    code_meaning: text(Some(meaning_column)),
+                },
+                definition: text(definition_column),
+                notes: text(notes_column),
+            });
+        }
+
+        Ok(registry)
+    }
+
+    /// Returns all templates, i.e. the contents of all sections labelled "TID
+    /// n".
+    ///
+    /// Type, order and root are read from the properties at the start of each
+    /// section like for context groups. The rows are read from the first
+    /// table of the section and nested below the preceding row with a lower
+    /// nesting level ("NL" column, e.g. ">>").
+    ///
+    /// # Errors
+    ///
+    /// This function fails if parsing of the part16.xml fails.
+    pub fn parse_template_registry(&self) -> Result<TemplateRegistry, Box<dyn Error>> {
+        let root = self.document.root()?;
+        let mut sections = Vec::new();
+        collect_sections(root, "TID ", &mut sections);
+
+        let templates = sections
+            .into_iter()
+            .filter_map(|(number, section)| {
+                number.parse().ok().map(|tid| Self::template(tid, section))
+            })
+            .collect::<Vec<_>>();
+        Ok(TemplateRegistry::from_templates(templates))
+    }
+
+    fn template(tid: u32, section: &xmltree::Element) -> Template {
+        let mut template = Template {
+            tid,
+            name: section
+                .get_child("title")
+                .map(table::element_text)
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+
+        for (key, value) in properties(section) {
+            match key.as_ref() {
+                "Type" => template.extensible = value == "Extensible",
+                "Order" => template.order_significant = value == "Significant",
+                "Root" => template.root = value == "Yes",
+                _ => {}
+            }
+        }
+
+        let table = match table::list_tables(section).into_iter().next() {
+            Some((info, element)) => Table::from_element(element, info),
+            None => return template,
+        };
+        let nesting_column = table.column("NL");
+        let relationship_column = table.column("Rel with Parent");
+        let value_type_column = table.column("VT");
+        let concept_name_column = table.column("Concept Name");
+        let vm_column = table.column("VM");
+        let requirement_column = table.column("Req Type");
+        let condition_column = table.column("Condition");
+        let constraint_column = table.column("Value Set Constraint");
+        for row in &table.rows {
+            let cell = |column: Option<usize>| column.and_then(|c| row.get(c));
+            let text = |column: Option<usize>| {
+                cell(column)
+                    .map(|cell| cell.text.trim().to_owned())
+                    .unwrap_or_default()
+            };
+            let link_number = |column: Option<usize>, prefix: &str| {
+                cell(column).and_then(|cell| {
+                    cell.links
+                        .iter()
+                        .filter_map(|link| link.strip_prefix(prefix))
+                        .filter_map(|number| number.parse().ok())
+                        .next()
+                })
+            };
+
+            // the number of a linked template or context group is not part of
+            // the text (e.g. "DCID <xref linkend="sect_CID_5000"/>")
+            let text_with_link = |column: Option<usize>, number: Option<u32>| {
+                let text = text(column);
+                match number {
+                    Some(number) if !text.contains(&number.to_string()) => {
+                        format!("{} {}", text, number).trim().to_owned()
+                    }
+                    _ => text,
+                }
+            };
+
+            let included_template = link_number(concept_name_column, "sect_TID_");
+            let value_set_cid = link_number(constraint_column, "sect_CID_");
+            let item = TemplateItem {
+                relationship: text(relationship_column),
+                value_type: text(value_type_column).parse().ok(),
+                concept_name: text_with_link(concept_name_column, included_template),
+                vm: text(vm_column),
+                requirement_type: text(requirement_column).parse().ok(),
+                condition: text(condition_column),
+                value_set_constraint: text_with_link(constraint_column, value_set_cid),
+                included_template,
+                value_set_cid,
+                children: Vec::new(),
+            };
+            let depth = text(nesting_column)
+                .chars()
+                .take_while(|&c| c == '>')
+                .count();
+            Self::insert_item(&mut template.items, depth, item);
+        }
+
+        template
+    }
+
+    /// Adds `item` to the children of the last row at nesting level `depth`
+    /// of `items`, or to the innermost existing level if there is none.
+    fn insert_item(items: &mut Vec<TemplateItem>, depth: usize, item: TemplateItem) {
+        if depth > 0 {
+            if let Some(parent) = items.last_mut() {
+                return Self::insert_item(&mut parent.children, depth - 1, item);
+            }
+        }
+
+        items.push(item);
+    }
+
+    fn context_group(cid: u32, section: &xmltree::Element) -> ContextGroup {
+        let mut group = ContextGroup {
+            cid,
+            name: section
+                .get_child("title")
+                .map(table::element_text)
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+
+        for (key, value) in properties(section) {
+            match key.as_ref() {
+                "Keyword" => group.keyword = value,
+                "UID" => group.uid = Some(value.replace("\u{200b}", "")),
+                "Type" => group.extensible = value == "Extensible",
+                "Version" => group.version = value,
+                _ => {}
+            }
+        }
+
+        let table = match table::list_tables(section).into_iter().next() {
+            Some((info, element)) => Table::from_element(element, info),
+            None => return group,
+        };
+        let scheme_column = table.column("Coding Scheme Designator");
+        let value_column = table.column("Code Value");
+        let meaning_column = table.column("Code Meaning");
+        for row in &table.rows {
+            let first = match row.first() {
+                Some(cell) => cell,
+                None => continue,
+            };
+
+            if first.text.starts_with("Include") {
+                let cid = first
+                    .links
+                    .iter()
+                    .filter_map(|link| link.trim_start_matches("sect_CID_").parse().ok())
+                    .next();
+                if let Some(cid) = cid {
+                    group.members.push(ContextGroupMember::Include(cid));
+                }
+                continue;
+            }
+
+            let text = |column: Option<usize>| {
+                column
+                    .and_then(|c| row.get(c))
+                    .map(|cell| cell.text.clone())
+                    .unwrap_or_default()
+            };
+            group
+                .members
+                .push(ContextGroupMember::Concept(CodedConcept {
+                    scheme_designator: text(scheme_column),
+                    code_value: text(value_column),
+                    code_meaning: text(meaning_column),
+                }));
+        }
+
+        group
+    }
+}
+
+/// Collects all sections in `element` whose label starts with `prefix` (e.g.
+/// "CID ") along with the rest of their label (e.g. "7021").
+pub(crate) fn collect_sections<'a>(
+    element: &'a xmltree::Element,
+    prefix: &str,
+    sections: &mut Vec<(String, &'a xmltree::Element)>,
+) {
+    for child in &element.children {
+        if child.name == "section" {
+            let number = child
+                .attributes
+                .get("label")
+                .and_then(|label| label.strip_prefix(prefix));
+            if let Some(number) = number {
+                sections.push((number.trim().to_owned(), child));
+                continue;
+            }
+        }
+
+        collect_sections(child, prefix, sections);
+    }
+}
+
+/// Returns the properties (e.g. "Type" and "Version") listed at the start of
+/// `section`, either as "variablelist" entries or as paragraphs of the form
+/// "Type: Extensible Version: 20030108".
+pub(crate) fn properties(section: &xmltree::Element) -> Vec<(String, String)> {
+    let mut properties = Vec::new();
+    for child in &section.children {
+        match child.name.as_ref() {
+            "variablelist" => {
+                for entry in child.children.iter().filter(|c| c.name == "varlistentry") {
+                    let key = entry.get_child("term").map(table::element_text);
+                    let value = entry.get_child("listitem").map(table::element_text);
+                    if let (Some(key), Some(value)) = (key, value) {
+                        properties.push((key.trim_end_matches(':').trim().to_owned(), value));
+                    }
+                }
+            }
+            "para" => {
+                let text = table::element_text(child);
+                let words: Vec<&str> = text.split_whitespace().collect();
+                for (index, word) in words.iter().enumerate() {
+                    if word.ends_with(':') && index + 1 < words.len() {
+                        properties.push((
+                            word.trim_end_matches(':').to_owned(),
+                            words[index + 1].to_owned(),
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    properties
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fixtures::fixture;
+    use template::{RequirementType, ValueType};
+    use {ContextGroupUID, Kind, TemplateUID, UID};
+
+    #[test]
+    fn parse_context_group_registry_reads_properties_and_members() {
+        let registry = ContentMappingParser::with_part16_file_contents(fixture(16).to_owned())
+            .parse_context_group_registry()
+            .unwrap();
+        assert_eq!(registry.len(), 2);
+
+        let anatomic_modifier = registry.get(2).unwrap();
+        assert_eq!(anatomic_modifier.name, "Anatomic Modifier");
+        assert_eq!(anatomic_modifier.keyword, "AnatomicModifier");
+        assert_eq!(
+            anatomic_modifier.uid,
+            Some("1.2.840.10008.6.1.1".to_owned())
+        );
+        assert!(anatomic_modifier.extensible);
+        assert_eq!(anatomic_modifier.version, "20181121");
+
+        let titles = registry.get(7021).unwrap();
+        assert!(!titles.extensible);
+        assert_eq!(titles.version, "20030108");
+        assert_eq!(titles.members[1], ContextGroupMember::Include(2));
+
+        let concepts: Vec<String> = registry
+            .concepts(7021)
+            .into_iter()
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(
+            concepts,
+            vec![
+                "(126000, DCM, \"Imaging Measurement Report\")",
+                "(7771000, SCT, \"Left\")",
+                "(24028007, SCT, \"Right\")",
+            ]
+        );
+    }
+
+    #[test]
+    fn context_groups_are_resolved_from_context_group_uids() {
+        let registry = ContentMappingParser::with_part16_file_contents(fixture(16).to_owned())
+            .parse_context_group_registry()
+            .unwrap();
+        let uid = ContextGroupUID {
+            value: "1.2.840.10008.6.1.530".to_owned(),
+            context_identifier: "CID 7021".to_owned(),
+            name: "Measurement Report Document Titles".to_owned(),
+        };
+        assert_eq!(registry.resolve(&uid).unwrap().cid, 7021);
+
+        let by_value = ContextGroupUID {
+            value: "1.2.840.10008.6.1.1".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(registry.resolve(&by_value).unwrap().cid, 2);
+    }
+
+    #[test]
+    fn parse_dcm_code_registry_looks_up_by_value_and_meaning() {
+        let mut registry = ContentMappingParser::with_part16_file_contents(fixture(16).to_owned())
+            .parse_dcm_code_registry()
+            .unwrap();
+        assert_eq!(registry.len(), 4);
+        assert_eq!(registry.scheme_designator(), "DCM");
+
+        let report = registry.get("126000").unwrap();
+        assert_eq!(report.concept.code_meaning, "Imaging Measurement Report");
+        assert!(report.definition.starts_with("A report of measurements"));
+        assert!(registry.is_valid(&report.concept));
+        assert_eq!(registry.find_by_meaning("Finding").len(), 2);
+        assert_eq!(registry.get("128773").unwrap().notes, "Retired");
+
+        let uids = vec![UID {
+            value: "1.2.840.10008.2.16.4".to_owned(),
+            full_name: "DICOM Controlled Terminology".to_owned(),
+            normalized_name: "DICOM Controlled Terminology".to_owned(),
+            kind: Kind::CodingScheme,
+        }];
+        assert!(registry.link_scheme_uid(&uids, "DICOM Controlled Terminology"));
+        assert_eq!(registry.scheme_uid(), Some("1.2.840.10008.2.16.4"));
+    }
+
+    #[test]
+    fn parse_template_registry_nests_rows_by_nesting_level() {
+        let registry = ContentMappingParser::with_part16_file_contents(fixture(16).to_owned())
+            .parse_template_registry()
+            .unwrap();
+        assert_eq!(registry.len(), 2);
+
+        let report = registry.get(1500).unwrap();
+        assert_eq!(report.name, "Measurement Report");
+        assert!(report.extensible);
+        assert!(report.order_significant);
+        assert!(report.root);
+        assert_eq!(report.items.len(), 1);
+
+        let root = &report.items[0];
+        assert_eq!(root.value_type, Some(ValueType::Container));
+        assert_eq!(
+            root.concept_name_code().unwrap().to_string(),
+            "(126000, DCM, \"Imaging Measurement Report\")"
+        );
+        assert_eq!(root.children.len(), 2);
+        assert_eq!(root.children[0].value_type, Some(ValueType::Include));
+        assert_eq!(root.children[0].included_template, Some(1204));
+
+        let measurements = &root.children[1];
+        assert_eq!(measurements.relationship, "CONTAINS");
+        assert_eq!(
+            measurements.requirement_type,
+            Some(RequirementType::MandatoryConditional)
+        );
+        assert!(measurements.condition.starts_with("At least one"));
+        assert_eq!(measurements.children[0].vm, "1-n");
+
+        let uid = TemplateUID {
+            value: "1.2.840.10008.8.1.1".to_owned(),
+            template_identifier: "TID 1500".to_owned(),
+            name: "Measurement Report".to_owned(),
+        };
+        assert_eq!(registry.resolve(&uid).unwrap().tid, 1500);
+    }
+
+    #[test]
+    fn template_items_expand_included_templates() {
+        let registry = ContentMappingParser::with_part16_file_contents(fixture(16).to_owned())
+            .parse_template_registry()
+            .unwrap();
+        let items: Vec<(usize, &str)> = registry
+            .items(1500)
+            .into_iter()
+            .map(|(depth, item)| (depth, item.concept_name.as_str()))
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                (0, "EV (126000, DCM, \"Imaging Measurement Report\")"),
+                (
+                    1,
+                    "EV (121049, DCM, \"Language of Content Item and Descendants\")"
+                ),
+                (1, "EV (126010, DCM, \"Imaging Measurements\")"),
+                (2, "DTID 1501"),
+            ]
+        );
+
+        let language = &registry.get(1204).unwrap().items[0];
+        assert_eq!(language.value_set_cid, Some(5000));
+        assert_eq!(language.value_set_constraint, "DCID 5000");
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+
+use code::CodedConcept;
+use uid::ContextGroupUID;
+
+/// A member of a context group.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ContextGroupMember {
+    /// A single coded concept.
+    Concept(CodedConcept),
+
+    /// All members of the context group with the given CID.
+    Include(u32),
+}
+
+/// A context group as defined in Annex B "DCMR Context Groups" of part 16
+/// (e.g. "CID 7021 Measurement Report Document Titles").
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ContextGroup {
+    /// The context group identifier (e.g. 7021 for "CID 7021").
+    pub cid: u32,
+
+    /// The name of the context group (e.g. "Measurement Report Document
+    /// Titles").
+    pub name: String,
+
+    /// The keyword of the context group (e.g. "MeasurementReportDocumentTitle"),
+    /// empty for editions without keywords.
+    pub keyword: String,
+
+    /// The UID of the context group, if given.
+    pub uid: Option<String>,
+
+    /// Whether the context group may be extended with further concepts.
+    pub extensible: bool,
+
+    /// The version of the context group (e.g. "20030108").
+    pub version: String,
+
+    /// The members of the context group in the order of the standard.
+    pub members: Vec<ContextGroupMember>,
+}
+
+/// All context groups of part 16 keyed by their CID.
+#[derive(Clone, Debug, Default)]
+pub struct ContextGroupRegistry {
+    groups: BTreeMap<u32, ContextGroup>,
+}
+
+impl ContextGroupRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new `ContextGroupRegistry` containing `groups`.
+    pub fn from_groups<I>(groups: I) -> Self
+    where
+        I: IntoIterator<Item = ContextGroup>,
+    {
+        ContextGroupRegistry {
+            groups: groups.into_iter().map(|group| (group.cid, group)).collect(),
+        }
+    }
+
+    /// Returns the context group with the given `cid`.
+    pub fn get(&self, cid: u32) -> Option<&ContextGroup> {
+        self.groups.get(&cid)
+    }
+
+    /// Returns the context group of a UID listed in the "Context Group UID
+    /// Values" table of part 6.
+    pub fn resolve(&self, uid: &ContextGroupUID) -> Option<&ContextGroup> {
+        self.groups
+            .values()
+            .find(|group| group.uid.as_ref() == Some(&uid.value))
+            .or_else(|| uid.cid().and_then(|cid| self.get(cid)))
+    }
+
+    /// Returns all concepts of the context group `cid` with all included
+    /// context groups replaced by their concepts.
+    pub fn concepts(&self, cid: u32) -> Vec<&CodedConcept> {
+        let mut concepts = Vec::new();
+        self.collect_concepts(cid, &mut Vec::new(), &mut concepts);
+        concepts
+    }
+
+    /// Returns all context groups ordered by CID.
+    pub fn groups(&self) -> impl Iterator<Item = &ContextGroup> {
+        self.groups.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    fn collect_concepts<'a>(
+        &'a self,
+        cid: u32,
+        visited: &mut Vec<u32>,
+        concepts: &mut Vec<&'a CodedConcept>,
+    ) {
+        // guard against context groups (indirectly) including themselves
+        if visited.contains(&cid) {
+            return;
+        }
+        visited.push(cid);
+
+        if let Some(group) = self.groups.get(&cid) {
+            for member in &group.members {
+                match *member {
+                    ContextGroupMember::Concept(ref concept) => concepts.push(concept),
+                    ContextGroupMember::Include(cid) => {
+                        self.collect_concepts(cid, visited, concepts)
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,10 @@
+use std::error::Error;
 use std::fmt;
 
+use keyword::Keyword;
+use tag::TagRange;
+use vr::VR;
+
 /// A unit of information as defined by a single entry in the DICOM data dictionary.
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub struct DataElement {
@@ -35,6 +40,50 @@ impl DataElement {
     pub fn new() -> DataElement {
         Default::default()
     }
+
+    /// Returns the typed form of `tag`, which may stand for a range of tags
+    /// (e.g. "(60xx,3000)").
+    ///
+    /// # Errors
+    ///
+    /// This function fails if `tag` is not in the format "(gggg,eeee)".
+    pub fn tag_range(&self) -> Result<TagRange, Box<dyn Error>> {
+        self.tag.parse()
+    }
+
+    /// Returns `tag` in the format of `TagRange` (e.g. "(60xx,3000)" for
+    /// "(60XX,3000)") or unchanged if it cannot be parsed, e.g. to match the
+    /// elements of different editions by tag.
+    pub(crate) fn normalized_tag(&self) -> String {
+        self.tag_range()
+            .map(|t| t.to_string())
+            .unwrap_or_else(|_| self.tag.clone())
+    }
+
+    /// Returns the typed form of `vr`. Elements with alternative VRs (e.g.
+    /// "US or SS") return all alternatives, elements without VR (e.g. "Item")
+    /// return an empty list.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if `vr` contains an unknown VR.
+    pub fn vrs(&self) -> Result<Vec<VR>, Box<dyn Error>> {
+        VR::parse_list(&self.vr)
+    }
+
+    /// Returns the words of `keyword`, e.g. to render it as an identifier.
+    pub fn parsed_keyword(&self) -> Keyword {
+        Keyword::new(&self.keyword)
+    }
+
+    /// Returns whether the data element is retired (i.e. its comment is
+    /// "RET").
+    pub fn is_retired(&self) -> bool {
+        match self.comment {
+            Some(ref c) => c.starts_with("RET"),
+            None => false,
+        }
+    }
 }
 
 impl fmt::Display for DataElement {
@@ -0,0 +1,212 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use private_dictionary::PrivateDictionary;
+use tag::TagRange;
+use vr::VR;
+use DataElement;
+
+/// A parser for data dictionaries in the format of DCMTK's "dicom.dic".
+///
+/// Each non-comment line of such a file holds five tab-separated fields:
+/// tag, VR, name, VM and version (e.g. "(0008,0001)", "UL", "LengthToEnd",
+/// "1" and "ACR/NEMA2"). Repeating groups are given as ranges of group or element
+/// numbers (e.g. "(6000-60ff,3000)"), private tags include their private
+/// creator (e.g. "(0019,"SIEMENS MR HEADER",0a)").
+pub struct DcmtkParser {
+    /// Holds the contents of the dicom.dic file once read.
+    content: String,
+}
+
+impl DcmtkParser {
+    /// Creates a new `DcmtkParser` instance using the dicom.dic given as
+    /// `file_path`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Opening the file at `file_path` fails
+    /// * Reading the file at `file_path` fails
+    pub fn with_dicom_dic_file(file_path: &Path) -> Result<Self, ::std::io::Error> {
+        Ok(Self {
+            content: fs::read_to_string(file_path)?,
+        })
+    }
+
+    /// Creates a new `DcmtkParser` instance given the full `contents` of a
+    /// dicom.dic file.
+    pub fn with_dicom_dic_file_contents(contents: String) -> Self {
+        Self { content: contents }
+    }
+
+    /// Returns all public data elements defined in the dictionary. Entries of
+    /// private tags (i.e. those including a private creator) are skipped, use
+    /// `parse_private_dictionary` for those.
+    ///
+    /// The returned elements use the same conventions as the ones returned by
+    /// `Parser`:
+    ///
+    /// * ranges are given with wildcards (e.g. "(6000-60ff,3000)" becomes
+    ///   "(60xx,3000)") where possible, odd and unrestricted ranges keep their
+    ///   bounds (e.g. "(0009-o-FFFF,0010-u-00FF)")
+    /// * alternative VRs are separated by " or " (e.g. "ox" becomes "OB or OW")
+    /// * retired elements have the comment "RET"
+    ///
+    /// Since dicom.dic only contains the keyword of an element, the `name` of
+    /// the returned elements is empty and their `keyword` does not contain any
+    /// zero-width spaces.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if a line does not follow the dicom.dic format
+    /// (e.g. has too few fields, an invalid tag or an unknown VR).
+    pub fn parse_data_elements(&self) -> Result<Vec<DataElement>, Box<dyn Error>> {
+        let mut data_elements = Vec::new();
+        for (line_number, fields) in self.entries() {
+            if fields[0].contains('"') {
+                continue;
+            }
+
+            let data_element = Self::parse_data_element(&fields)
+                .map_err(|e| format!("Unable to parse line {} of dicom.dic: {}", line_number, e))?;
+            data_elements.push(data_element);
+        }
+
+        Ok(data_elements)
+    }
+
+    /// Returns all private data elements defined in the dictionary (i.e. those
+    /// whose tag includes a private creator, e.g. "(0019,"SIEMENS MR
+    /// HEADER",0a)").
+    ///
+    /// # Errors
+    ///
+    /// This function fails if a line of a private tag does not follow the
+    /// dicom.dic format.
+    pub fn parse_private_dictionary(&self) -> Result<PrivateDictionary, Box<dyn Error>> {
+        PrivateDictionary::with_file_contents(&self.content)
+    }
+
+    /// Returns the line number and fields of each non-comment line.
+    fn entries(&self) -> Vec<(usize, Vec<&str>)> {
+        self.content
+            .lines()
+            .enumerate()
+            .filter(|&(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .map(|(index, line)| (index + 1, line.split('\t').map(str::trim).collect()))
+            .collect()
+    }
+
+    fn parse_data_element(fields: &[&str]) -> Result<DataElement, Box<dyn Error>> {
+        if fields.len() < 4 {
+            return Err(From::from(format!(
+                "Expected at least 4 fields, found {}",
+                fields.len()
+            )));
+        }
+
+        let mut data_element = DataElement::new();
+        data_element.tag = Self::parse_tag(fields[0])?.to_string();
+        data_element.vr = VR::format_list(&Self::parse_vr(fields[1])?);
+        data_element.vm = fields[3].to_owned();
+
+        let version = fields.get(4).map_or("", |v| *v);
+        let name = fields[2];
+        if name.starts_with("RETIRED_") || version.to_lowercase().contains("retired") {
+            data_element.keyword = name.trim_start_matches("RETIRED_").to_owned();
+            data_element.comment = Some("RET".to_owned());
+        } else {
+            data_element.keyword = name.to_owned();
+        }
+
+        Ok(data_element)
+    }
+
+    /// Parses a tag of a public element which may define ranges of group
+    /// and/or element numbers (e.g. "(6000-60ff,3000)", "(0020,3100-31ff)" or
+    /// "(0009-o-ffff,0010-u-00ff)").
+    pub(crate) fn parse_tag(tag: &str) -> Result<TagRange, Box<dyn Error>> {
+        tag.parse()
+    }
+
+    /// Parses a VR, including DCMTK's pseudo VRs for ambiguous or missing
+    /// VRs (e.g. "xs" for "US or SS" or "na" for none).
+    pub(crate) fn parse_vr(vr: &str) -> Result<Vec<VR>, Box<dyn Error>> {
+        match vr {
+            "ox" | "px" => Ok(vec![VR::OB, VR::OW]),
+            "xs" => Ok(vec![VR::US, VR::SS]),
+            "lt" => Ok(vec![VR::US, VR::SS, VR::OW]),
+            "up" => Ok(vec![VR::UL]),
+            "na" | "" => Ok(Vec::new()),
+            _ => vr.split('/').map(|vr| vr.parse()).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tag::Tag;
+
+    #[test]
+    fn parse_data_elements_converts_ranges_vrs_and_retired_entries() {
+        let parser = DcmtkParser::with_dicom_dic_file_contents(
+            "# comment\n\
+             (0008,0001)\tUL\tRETIRED_LengthToEnd\t1\tACR/NEMA2\n\
+             (6000-60ff,3000)\tox\tOverlayData\t1\tDICOM\n\
+             (0020,3100-31ff)\tCS\tSourceImageIDs\t1-n\tDICOM/retired\n\
+             (0019,\"SIEMENS MR HEADER\",08)\tCS\tImagingMode\t1\tPrivateTag\n"
+                .to_owned(),
+        );
+
+        let elements = parser.parse_data_elements().unwrap();
+        assert_eq!(elements.len(), 3);
+
+        assert_eq!(elements[0].tag, "(0008,0001)");
+        assert_eq!(elements[0].keyword, "LengthToEnd");
+        assert_eq!(elements[0].comment, Some("RET".to_owned()));
+
+        assert_eq!(elements[1].tag, "(60xx,3000)");
+        assert_eq!(elements[1].vr, "OB or OW");
+        assert!(elements[1].comment.is_none());
+
+        assert_eq!(elements[2].tag, "(0020,31xx)");
+        assert_eq!(elements[2].vm, "1-n");
+        assert_eq!(elements[2].comment, Some("RET".to_owned()));
+    }
+
+    #[test]
+    fn parse_data_elements_keeps_odd_and_unrestricted_ranges() {
+        let parser = DcmtkParser::with_dicom_dic_file_contents(
+            "(0009-o-FFFF,0000)\tUL\tPrivateGroupLength\t1\tPRIVATE\n\
+             (0009-o-FFFF,0010-u-00FF)\tLO\tPrivateCreator\t1\tPRIVATE\n\
+             (0000-u-ffff,0000)\tUL\tGenericGroupLength\t1\tGENERIC\n"
+                .to_owned(),
+        );
+
+        let elements = parser.parse_data_elements().unwrap();
+        let tags: Vec<&str> = elements.iter().map(|e| e.tag.as_str()).collect();
+        assert_eq!(
+            tags,
+            vec![
+                "(0009-o-FFFF,0000)",
+                "(0009-o-FFFF,0010-u-00FF)",
+                "(xxxx,0000)"
+            ]
+        );
+
+        let private_creator = elements[1].tag_range().unwrap();
+        assert!(private_creator.contains(Tag::new(0x0019, 0x0010)));
+        assert!(!private_creator.contains(Tag::new(0x0018, 0x0010)));
+    }
+
+    #[test]
+    fn parse_data_elements_reports_line_of_invalid_entry() {
+        let parser =
+            DcmtkParser::with_dicom_dic_file_contents("\n(0008,0001)\tZZ\tFoo\t1\n".to_owned());
+        let error = parser.parse_data_elements().unwrap_err();
+        assert!(error.to_string().contains("line 2"));
+    }
+}
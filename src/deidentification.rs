@@ -0,0 +1,371 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use dictionary::Dictionary;
+use tag::{Tag, TagRange};
+use DataElement;
+
+/// An action of the Attribute Confidentiality Profiles applied to an
+/// attribute when de-identifying a data set.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Action {
+    /// Replace with a non-zero length dummy value ("D").
+    Dummy,
+
+    /// Replace with a zero length or dummy value ("Z").
+    Zero,
+
+    /// Remove ("X").
+    Remove,
+
+    /// Keep ("K").
+    Keep,
+
+    /// Clean, i.e. replace identifying information with values of similar
+    /// meaning ("C").
+    Clean,
+
+    /// Replace with a consistent non-zero length UID ("U").
+    ReplaceUid,
+
+    /// "Z" unless "D" is required to maintain IOD conformance ("Z/D").
+    ZeroOrDummy,
+
+    /// "X" unless "Z" is required to maintain IOD conformance ("X/Z").
+    RemoveOrZero,
+
+    /// "X" unless "D" is required to maintain IOD conformance ("X/D").
+    RemoveOrDummy,
+
+    /// "X" unless "Z" or "D" is required to maintain IOD conformance
+    /// ("X/Z/D").
+    RemoveOrZeroOrDummy,
+
+    /// "X" unless "Z" or replacement of contained instance UIDs ("U") is
+    /// required to maintain IOD conformance ("X/Z/U*").
+    RemoveOrZeroOrReplaceUid,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            Action::Dummy => "D",
+            Action::Zero => "Z",
+            Action::Remove => "X",
+            Action::Keep => "K",
+            Action::Clean => "C",
+            Action::ReplaceUid => "U",
+            Action::ZeroOrDummy => "Z/D",
+            Action::RemoveOrZero => "X/Z",
+            Action::RemoveOrDummy => "X/D",
+            Action::RemoveOrZeroOrDummy => "X/Z/D",
+            Action::RemoveOrZeroOrReplaceUid => "X/Z/U*",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for Action {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "D" => Ok(Action::Dummy),
+            "Z" => Ok(Action::Zero),
+            "X" => Ok(Action::Remove),
+            "K" => Ok(Action::Keep),
+            "C" => Ok(Action::Clean),
+            "U" => Ok(Action::ReplaceUid),
+            "Z/D" => Ok(Action::ZeroOrDummy),
+            "X/Z" => Ok(Action::RemoveOrZero),
+            "X/D" => Ok(Action::RemoveOrDummy),
+            "X/Z/D" => Ok(Action::RemoveOrZeroOrDummy),
+            "X/Z/U" | "X/Z/U*" => Ok(Action::RemoveOrZeroOrReplaceUid),
+            _ => Err(From::from(format!("Unknown action code '{}'", s))),
+        }
+    }
+}
+
+/// An option of the Basic Application Level Confidentiality Profile, each
+/// overriding the action of the basic profile for some attributes.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ProfileOption {
+    RetainSafePrivate,
+    RetainUids,
+    RetainDeviceIdentity,
+    RetainInstitutionIdentity,
+    RetainPatientCharacteristics,
+    RetainLongitudinalFullDates,
+    RetainLongitudinalModifiedDates,
+    CleanDescriptors,
+    CleanStructuredContent,
+    CleanGraphics,
+}
+
+impl ProfileOption {
+    /// Returns the option of the column with the abbreviated `header` used in
+    /// Table E.1-1 (e.g. "Rtn. Safe Priv. Opt.").
+    pub fn from_header(header: &str) -> Option<Self> {
+        let header = header.trim();
+        if header.starts_with("Rtn. Safe Priv") {
+            Some(ProfileOption::RetainSafePrivate)
+        } else if header.starts_with("Rtn. UIDs") {
+            Some(ProfileOption::RetainUids)
+        } else if header.starts_with("Rtn. Dev") {
+            Some(ProfileOption::RetainDeviceIdentity)
+        } else if header.starts_with("Rtn. Inst") {
+            Some(ProfileOption::RetainInstitutionIdentity)
+        } else if header.starts_with("Rtn. Pat") {
+            Some(ProfileOption::RetainPatientCharacteristics)
+        } else if header.starts_with("Rtn. Long. Full") {
+            Some(ProfileOption::RetainLongitudinalFullDates)
+        } else if header.starts_with("Rtn. Long. Modif") {
+            Some(ProfileOption::RetainLongitudinalModifiedDates)
+        } else if header.starts_with("Clean Desc") {
+            Some(ProfileOption::CleanDescriptors)
+        } else if header.starts_with("Clean Struct") {
+            Some(ProfileOption::CleanStructuredContent)
+        } else if header.starts_with("Clean Graph") {
+            Some(ProfileOption::CleanGraphics)
+        } else {
+            None
+        }
+    }
+}
+
+/// The actions of the Attribute Confidentiality Profiles for a single
+/// attribute (or range of attributes).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ConfidentialityAttribute {
+    /// The name of the attribute (e.g. "Patient's Name").
+    pub name: String,
+
+    /// The tag of the attribute as given in the table (e.g. "(0010,0010)" or
+    /// "(50xx,xxxx)").
+    pub tag: String,
+
+    /// Whether the attribute is retired according to part 6.
+    pub retired: bool,
+
+    /// Whether the attribute is used in a standard composite IOD according to
+    /// part 3.
+    pub in_standard_iod: bool,
+
+    /// The action of the basic profile, `None` if the cell is empty or
+    /// unknown.
+    pub basic_profile: Option<Action>,
+
+    /// The actions of the options which override the basic profile.
+    pub options: BTreeMap<ProfileOption, Action>,
+}
+
+impl ConfidentialityAttribute {
+    /// Returns the typed form of `tag`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if `tag` is not in the format "(gggg,eeee)".
+    pub fn tag_range(&self) -> Result<TagRange, Box<dyn Error>> {
+        self.tag.parse()
+    }
+
+    /// Returns the action to apply with the given `options` enabled. The
+    /// action of the first enabled option with an action of its own is used,
+    /// otherwise the one of the basic profile.
+    pub fn action(&self, options: &[ProfileOption]) -> Option<Action> {
+        options
+            .iter()
+            .filter_map(|option| self.options.get(option))
+            .next()
+            .cloned()
+            .or(self.basic_profile)
+    }
+
+    /// Returns the data element of the attribute in `dictionary`.
+    pub fn data_element<'a>(&self, dictionary: &'a Dictionary) -> Option<&'a DataElement> {
+        let range = self.tag_range().ok()?;
+        if range.is_range() {
+            dictionary.get(&range)
+        } else {
+            dictionary.lookup(Tag::new(range.group, range.element))
+        }
+    }
+}
+
+/// A difference between the attributes of the confidentiality profile of
+/// part 15 and the data dictionary of part 6.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProfileMismatch {
+    /// The tag is not part of the data dictionary.
+    Unknown(String),
+
+    /// The attribute is named differently (tag, name in part 15, name in part
+    /// 6).
+    NameDiffers(String, String, String),
+
+    /// The attribute is retired in only one of both parts (tag, retired
+    /// according to part 15).
+    RetiredDiffers(String, bool),
+}
+
+impl fmt::Display for ProfileMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ProfileMismatch::Unknown(ref tag) => {
+                write!(f, "{} is not part of the data dictionary", tag)
+            }
+            ProfileMismatch::NameDiffers(ref tag, ref part15, ref part6) => write!(
+                f,
+                "{} is named '{}' in part 6 instead of '{}'",
+                tag, part6, part15
+            ),
+            ProfileMismatch::RetiredDiffers(ref tag, retired) => write!(
+                f,
+                "{} is {}retired in part 6",
+                tag,
+                if retired { "not " } else { "" }
+            ),
+        }
+    }
+}
+
+/// All attributes of the Attribute Confidentiality Profiles keyed by their
+/// tag.
+#[derive(Clone, Debug, Default)]
+pub struct DeidentificationRegistry {
+    attributes: BTreeMap<TagRange, ConfidentialityAttribute>,
+}
+
+impl DeidentificationRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new `DeidentificationRegistry` from `attributes`. Attributes
+    /// whose tag is not in the format "(gggg,eeee)" (e.g. the row for all
+    /// private attributes) are skipped.
+    pub fn from_attributes<I>(attributes: I) -> Self
+    where
+        I: IntoIterator<Item = ConfidentialityAttribute>,
+    {
+        DeidentificationRegistry {
+            attributes: attributes
+                .into_iter()
+                .filter_map(|attribute| attribute.tag_range().ok().map(|tag| (tag, attribute)))
+                .collect(),
+        }
+    }
+
+    /// Returns the attribute stored for exactly the given `tag` (which may be
+    /// a range like "(50xx,xxxx)").
+    pub fn get(&self, tag: &TagRange) -> Option<&ConfidentialityAttribute> {
+        self.attributes.get(tag)
+    }
+
+    /// Returns the attribute for the concrete `tag`. If `tag` has no entry of
+    /// its own, the most specific range covering it is used (e.g.
+    /// "(50xx,xxxx)" for (5002,0010)).
+    pub fn lookup(&self, tag: Tag) -> Option<&ConfidentialityAttribute> {
+        if let Some(attribute) = self.attributes.get(&TagRange::single(tag)) {
+            return Some(attribute);
+        }
+
+        self.attributes
+            .iter()
+            .filter(|&(range, _)| range.is_range() && range.contains(tag))
+            .max_by_key(|&(range, _)| {
+                range.group_mask.count_ones() + range.element_mask.count_ones()
+            })
+            .map(|(_, attribute)| attribute)
+    }
+
+    /// Returns the action to apply to `tag` with the given `options` enabled,
+    /// see `ConfidentialityAttribute::action`.
+    pub fn action(&self, tag: Tag, options: &[ProfileOption]) -> Option<Action> {
+        self.lookup(tag)
+            .and_then(|attribute| attribute.action(options))
+    }
+
+    /// Returns all attributes ordered by tag.
+    pub fn attributes(&self) -> impl Iterator<Item = &ConfidentialityAttribute> {
+        self.attributes.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+
+    /// Compares all attributes with the data elements of `dictionary` (e.g.
+    /// built from part 6) and returns every mismatch.
+    pub fn cross_check(&self, dictionary: &Dictionary) -> Vec<ProfileMismatch> {
+        let mut mismatches = Vec::new();
+        for attribute in self.attributes.values() {
+            let element = match attribute.data_element(dictionary) {
+                Some(element) => element,
+                None => {
+                    mismatches.push(ProfileMismatch::Unknown(attribute.tag.clone()));
+                    continue;
+                }
+            };
+
+            if !element.name.is_empty() && element.name != attribute.name {
+                mismatches.push(ProfileMismatch::NameDiffers(
+                    attribute.tag.clone(),
+                    attribute.name.clone(),
+                    element.name.clone(),
+                ));
+            }
+
+            if element.is_retired() != attribute.retired {
+                mismatches.push(ProfileMismatch::RetiredDiffers(
+                    attribute.tag.clone(),
+                    attribute.retired,
+                ));
+            }
+        }
+
+        mismatches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_prefers_enabled_options_over_basic_profile() {
+        let mut attribute = ConfidentialityAttribute {
+            name: "Study Date".to_owned(),
+            tag: "(0008,0020)".to_owned(),
+            basic_profile: Some("Z".parse().unwrap()),
+            ..Default::default()
+        };
+        attribute
+            .options
+            .insert(ProfileOption::RetainLongitudinalFullDates, Action::Keep);
+        attribute.options.insert(
+            ProfileOption::RetainLongitudinalModifiedDates,
+            Action::Clean,
+        );
+
+        assert_eq!(attribute.action(&[]), Some(Action::Zero));
+        assert_eq!(
+            attribute.action(&[
+                ProfileOption::RetainUids,
+                ProfileOption::RetainLongitudinalModifiedDates
+            ]),
+            Some(Action::Clean)
+        );
+        assert_eq!(
+            "X/Z/U*".parse::<Action>().unwrap(),
+            Action::RemoveOrZeroOrReplaceUid
+        );
+        assert!("Y".parse::<Action>().is_err());
+    }
+}
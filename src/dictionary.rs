@@ -0,0 +1,500 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use tag::{Tag, TagRange};
+use vr::VR;
+use DataElement;
+
+/// A collection of data elements keyed by their tag, e.g. built from the
+/// registries returned by `Parser` or from dictionaries read with
+/// `DcmtkParser`/`PydicomParser`.
+#[derive(Clone, Debug, Default)]
+pub struct Dictionary {
+    elements: BTreeMap<TagRange, DataElement>,
+}
+
+impl Dictionary {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new `Dictionary` containing `elements`. If multiple elements
+    /// have the same tag, the last one is kept.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the tag of an element is not in the format
+    /// "(gggg,eeee)".
+    pub fn from_elements<I>(elements: I) -> Result<Self, Box<dyn Error>>
+    where
+        I: IntoIterator<Item = DataElement>,
+    {
+        let mut dictionary = Dictionary::new();
+        for element in elements {
+            dictionary.insert(element)?;
+        }
+
+        Ok(dictionary)
+    }
+
+    /// Inserts `element`, returning the element previously stored for the
+    /// same tag.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the tag of `element` is not in the format
+    /// "(gggg,eeee)".
+    pub fn insert(&mut self, element: DataElement) -> Result<Option<DataElement>, Box<dyn Error>> {
+        Ok(self.elements.insert(element.tag_range()?, element))
+    }
+
+    /// Returns the element stored for exactly the given `tag` (which may be a
+    /// range like "(60xx,3000)").
+    pub fn get(&self, tag: &TagRange) -> Option<&DataElement> {
+        self.elements.get(tag)
+    }
+
+    /// Returns the element defining the concrete `tag`. If `tag` has no entry
+    /// of its own, the most specific range covering it is used (e.g.
+    /// "(60xx,3000)" for (6002,3000)).
+    pub fn lookup(&self, tag: Tag) -> Option<&DataElement> {
+        if let Some(element) = self.elements.get(&TagRange::single(tag)) {
+            return Some(element);
+        }
+
+        self.elements
+            .iter()
+            .filter(|&(range, _)| range.is_range() && range.contains(tag))
+            .max_by_key(|&(range, _)| {
+                range.group_mask.count_ones() + range.element_mask.count_ones()
+            })
+            .map(|(_, element)| element)
+    }
+
+    /// Returns the VR to decode the value of `tag` with in an implicit VR
+    /// transfer syntax, or `None` if `tag` is unknown or has no VR (e.g.
+    /// items and delimiters).
+    ///
+    /// Ranges are resolved like in `lookup`. Tags without an entry of their
+    /// own get UL for group lengths (gggg,0000) and LO for private creators
+    /// (gggg,0010-00FF) of odd groups. Of multiple alternative VRs, OW is used
+    /// for "OB or OW" and similar as required by Annex A.1 of part 5, "US or
+    /// SS" is resolved with the Pixel Representation of `context`.
+    pub fn implicit_vr(&self, tag: Tag, context: &VrContext) -> Option<ImplicitVr> {
+        let element = match self.lookup(tag) {
+            Some(element) => element,
+            None if tag.is_group_length() => return Some(ImplicitVr::Resolved(VR::UL)),
+            None if tag.is_private_creator() => return Some(ImplicitVr::Resolved(VR::LO)),
+            None => return None,
+        };
+
+        let vrs = element.vrs().ok()?;
+        match vrs.len() {
+            0 => None,
+            1 => Some(ImplicitVr::Resolved(vrs[0])),
+            _ if vrs.contains(&VR::OW) => Some(ImplicitVr::Resolved(VR::OW)),
+            2 if vrs.contains(&VR::US) && vrs.contains(&VR::SS) => {
+                Some(match context.pixel_representation {
+                    Some(0) => ImplicitVr::Resolved(VR::US),
+                    Some(_) => ImplicitVr::Resolved(VR::SS),
+                    None => ImplicitVr::Ambiguous {
+                        candidates: vrs,
+                        depends_on: Some(PIXEL_REPRESENTATION),
+                    },
+                })
+            }
+            _ => Some(ImplicitVr::Ambiguous {
+                candidates: vrs,
+                depends_on: None,
+            }),
+        }
+    }
+
+    /// Returns a copy of this dictionary where every range (e.g.
+    /// "(60xx,3000)") is replaced by an entry for each concrete tag it covers
+    /// (see `TagRange::tags`). Concrete tags covered by multiple entries get
+    /// the most specific one like in `lookup`. Ranges of private groups (e.g.
+    /// "(0009-o-FFFF,0010-u-00FF)" of DCMTK's dicom.dic) are skipped.
+    pub fn flatten(&self) -> Dictionary {
+        let mut flattened = Dictionary::new();
+        for range in self.elements.keys() {
+            if range.is_range() && range.group_mask & range.group & 1 == 1 {
+                continue;
+            }
+
+            for tag in range.tags() {
+                let element = match self.lookup(tag) {
+                    Some(element) => element,
+                    None => continue,
+                };
+                flattened
+                    .elements
+                    .entry(TagRange::single(tag))
+                    .or_insert_with(|| DataElement {
+                        tag: tag.to_string(),
+                        ..element.clone()
+                    });
+            }
+        }
+
+        flattened
+    }
+
+    /// Returns all elements ordered by tag.
+    pub fn elements(&self) -> impl Iterator<Item = &DataElement> {
+        self.elements.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Merges the named `layers` (e.g. `[("standard", &standard,
+    /// Override::Replace), ("local", &local, Override::NonEmptyFields)]`) into
+    /// a single dictionary where later layers override earlier ones by tag as
+    /// chosen for each layer, see `Override`.
+    ///
+    /// Besides the merged dictionary, a conflict is reported for every tag and
+    /// field (name, keyword, VR or VM) with differing values between layers.
+    /// Empty values are not considered conflicting and keywords are compared
+    /// without zero-width spaces.
+    pub fn merge(layers: &[(&str, &Dictionary, Override)]) -> (Dictionary, Vec<Conflict>) {
+        let mut merged = Dictionary::new();
+        let mut definitions: BTreeMap<TagRange, Vec<(&str, &DataElement)>> = BTreeMap::new();
+        for &(layer, dictionary, mode) in layers {
+            for (tag, element) in &dictionary.elements {
+                definitions.entry(*tag).or_default().push((layer, element));
+
+                let merged_element = merged.elements.entry(*tag).or_default();
+                match mode {
+                    Override::Replace => *merged_element = element.clone(),
+                    Override::NonEmptyFields => Self::override_fields(merged_element, element),
+                }
+            }
+        }
+
+        let mut conflicts = Vec::new();
+        for (tag, definitions) in definitions {
+            for field in &[Field::Name, Field::Keyword, Field::VR, Field::VM] {
+                let values: Vec<(String, String)> = definitions
+                    .iter()
+                    .map(|&(layer, element)| (layer.to_owned(), field.value(element).to_owned()))
+                    .filter(|(_, value)| !value.is_empty())
+                    .collect();
+
+                let differs = values
+                    .iter()
+                    .any(|(_, value)| field.normalize(value) != field.normalize(&values[0].1));
+                if differs {
+                    conflicts.push(Conflict {
+                        tag,
+                        field: *field,
+                        values,
+                    });
+                }
+            }
+        }
+
+        (merged, conflicts)
+    }
+
+    fn override_fields(target: &mut DataElement, source: &DataElement) {
+        fn override_field(target: &mut String, source: &str) {
+            if !source.is_empty() {
+                *target = source.to_owned();
+            }
+        }
+
+        target.tag = source.tag.clone();
+        override_field(&mut target.name, &source.name);
+        override_field(&mut target.keyword, &source.keyword);
+        override_field(&mut target.vr, &source.vr);
+        override_field(&mut target.vm, &source.vm);
+        if source.comment.is_some() {
+            target.comment = source.comment.clone();
+        }
+    }
+}
+
+/// The tag of Pixel Representation, deciding between US and SS.
+const PIXEL_REPRESENTATION: Tag = Tag {
+    group: 0x0028,
+    element: 0x0103,
+};
+
+/// The values of the data set being decoded needed to resolve ambiguous VRs
+/// with `Dictionary::implicit_vr`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct VrContext {
+    /// The value of Pixel Representation (0028,0103), i.e. 0 for unsigned and
+    /// 1 for signed pixel values, if known.
+    pub pixel_representation: Option<u16>,
+}
+
+/// The VR of a tag in an implicit VR transfer syntax.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ImplicitVr {
+    /// The VR is known.
+    Resolved(VR),
+
+    /// The VR is one of `candidates`, depending on the value of the data
+    /// element `depends_on` (e.g. Pixel Representation for "US or SS"), if
+    /// the standard defines such a rule.
+    Ambiguous {
+        candidates: Vec<VR>,
+        depends_on: Option<Tag>,
+    },
+}
+
+/// How the elements of a layer of `Dictionary::merge` override the elements
+/// of earlier layers with the same tag.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Override {
+    /// The element replaces the earlier one as a whole, so empty fields and a
+    /// missing comment clear those of earlier layers (e.g. to un-retire an
+    /// element).
+    Replace,
+
+    /// Only the non-empty fields and the comment, if any, replace those of
+    /// the earlier element; the other fields keep their earlier values (e.g.
+    /// the name of elements read from a dicom.dic, which has no names).
+    NonEmptyFields,
+}
+
+/// A field of a data element that can conflict between dictionaries.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Field {
+    Name,
+    Keyword,
+    VR,
+    VM,
+}
+
+impl Field {
+    fn value<'a>(&self, element: &'a DataElement) -> &'a str {
+        match *self {
+            Field::Name => &element.name,
+            Field::Keyword => &element.keyword,
+            Field::VR => &element.vr,
+            Field::VM => &element.vm,
+        }
+    }
+
+    fn normalize(&self, value: &str) -> String {
+        match *self {
+            Field::Keyword => value.replace("\u{200b}", ""),
+            _ => value.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Field::Name => "Name",
+            Field::Keyword => "Keyword",
+            Field::VR => "VR",
+            Field::VM => "VM",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A field of a data element whose value differs between the layers of a
+/// merged dictionary.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Conflict {
+    /// The tag of the conflicting element.
+    pub tag: TagRange,
+
+    /// The conflicting field.
+    pub field: Field,
+
+    /// The name of each layer defining a value for the field together with
+    /// that value, in the order of the layers.
+    pub values: Vec<(String, String)>,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}: ", self.tag, self.field)?;
+        for (index, (layer, value)) in self.values.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}={}", layer, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(tag: &str, keyword: &str, vr: &str) -> DataElement {
+        DataElement {
+            tag: tag.to_owned(),
+            keyword: keyword.to_owned(),
+            vr: vr.to_owned(),
+            vm: "1".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn lookup_falls_back_to_most_specific_range() {
+        let dictionary = Dictionary::from_elements(vec![
+            element("(60xx,3000)", "OverlayData", "OB or OW"),
+            element("(60xx,xxxx)", "Overlay", ""),
+            element("(6000,3000)", "FirstOverlayData", "OW"),
+        ])
+        .unwrap();
+
+        let first = dictionary.lookup(Tag::new(0x6000, 0x3000)).unwrap();
+        assert_eq!(first.keyword, "FirstOverlayData");
+        let second = dictionary.lookup(Tag::new(0x6002, 0x3000)).unwrap();
+        assert_eq!(second.keyword, "OverlayData");
+        assert!(dictionary.lookup(Tag::new(0x6102, 0x3000)).is_none());
+    }
+
+    #[test]
+    fn flatten_replaces_ranges_with_concrete_tags() {
+        let dictionary = Dictionary::from_elements(vec![
+            element("(60xx,3000)", "OverlayData", "OB or OW"),
+            element("(6000,3000)", "FirstOverlayData", "OW"),
+            element("(0010,0010)", "PatientName", "PN"),
+        ])
+        .unwrap();
+
+        let flattened = dictionary.flatten();
+        assert_eq!(flattened.len(), 17);
+        assert!(flattened
+            .elements()
+            .all(|e| !e.tag_range().unwrap().is_range()));
+
+        let first = flattened.get(&Tag::new(0x6000, 0x3000).into()).unwrap();
+        assert_eq!(first.keyword, "FirstOverlayData");
+        let last = flattened.get(&Tag::new(0x601E, 0x3000).into()).unwrap();
+        assert_eq!(last.tag, "(601E,3000)");
+        assert_eq!(last.keyword, "OverlayData");
+        assert!(flattened.get(&Tag::new(0x6020, 0x3000).into()).is_none());
+    }
+
+    #[test]
+    fn implicit_vr_resolves_ranges_group_lengths_and_ambiguities() {
+        let dictionary = Dictionary::from_elements(vec![
+            element("(0028,0106)", "SmallestImagePixelValue", "US or SS"),
+            element("(60xx,3000)", "OverlayData", "OB or OW"),
+            element("(0010,0010)", "PatientName", "PN"),
+            element("(FFFE,E000)", "Item", ""),
+        ])
+        .unwrap();
+        let unknown = VrContext::default();
+
+        assert_eq!(
+            dictionary.implicit_vr(Tag::new(0x0010, 0x0010), &unknown),
+            Some(ImplicitVr::Resolved(VR::PN))
+        );
+        assert_eq!(
+            dictionary.implicit_vr(Tag::new(0x6002, 0x3000), &unknown),
+            Some(ImplicitVr::Resolved(VR::OW))
+        );
+        assert_eq!(
+            dictionary.implicit_vr(Tag::new(0x0018, 0x0000), &unknown),
+            Some(ImplicitVr::Resolved(VR::UL))
+        );
+        assert_eq!(
+            dictionary.implicit_vr(Tag::new(0x0009, 0x0010), &unknown),
+            Some(ImplicitVr::Resolved(VR::LO))
+        );
+        assert_eq!(
+            dictionary.implicit_vr(Tag::new(0xFFFE, 0xE000), &unknown),
+            None
+        );
+        assert_eq!(
+            dictionary.implicit_vr(Tag::new(0x0009, 0x1010), &unknown),
+            None
+        );
+
+        let smallest_pixel_value = Tag::new(0x0028, 0x0106);
+        assert_eq!(
+            dictionary.implicit_vr(smallest_pixel_value, &unknown),
+            Some(ImplicitVr::Ambiguous {
+                candidates: vec![VR::US, VR::SS],
+                depends_on: Some(Tag::new(0x0028, 0x0103)),
+            })
+        );
+        let signed = VrContext {
+            pixel_representation: Some(1),
+        };
+        assert_eq!(
+            dictionary.implicit_vr(smallest_pixel_value, &signed),
+            Some(ImplicitVr::Resolved(VR::SS))
+        );
+    }
+
+    #[test]
+    fn merge_overrides_non_empty_fields_and_reports_conflicts() {
+        let mut standard_element = element("(0008,0001)", "Length\u{200b}To\u{200b}End", "UL");
+        standard_element.name = "Length to End".to_owned();
+        let standard = Dictionary::from_elements(vec![
+            standard_element,
+            element("(0010,0010)", "PatientName", "PN"),
+        ])
+        .unwrap();
+        let local =
+            Dictionary::from_elements(vec![element("(0008,0001)", "LengthToEnd", "US")]).unwrap();
+
+        let (merged, conflicts) = Dictionary::merge(&[
+            ("standard", &standard, Override::Replace),
+            ("local", &local, Override::NonEmptyFields),
+        ]);
+        assert_eq!(merged.len(), 2);
+
+        let length_to_end = merged.lookup(Tag::new(0x0008, 0x0001)).unwrap();
+        assert_eq!(length_to_end.name, "Length to End");
+        assert_eq!(length_to_end.keyword, "LengthToEnd");
+        assert_eq!(length_to_end.vr, "US");
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].field, Field::VR);
+        assert_eq!(
+            conflicts[0].to_string(),
+            "(0008,0001) VR: standard=UL, local=US"
+        );
+    }
+
+    #[test]
+    fn merge_replacing_layer_un_retires_elements() {
+        let mut retired = element("(0008,0001)", "LengthToEnd", "UL");
+        retired.name = "Length to End".to_owned();
+        retired.comment = Some("RET".to_owned());
+        let standard = Dictionary::from_elements(vec![retired]).unwrap();
+        let local =
+            Dictionary::from_elements(vec![element("(0008,0001)", "LengthToEnd", "UL")]).unwrap();
+
+        let (filled_in, _) = Dictionary::merge(&[
+            ("standard", &standard, Override::Replace),
+            ("local", &local, Override::NonEmptyFields),
+        ]);
+        let length_to_end = filled_in.lookup(Tag::new(0x0008, 0x0001)).unwrap();
+        assert_eq!(length_to_end.comment, Some("RET".to_owned()));
+        assert_eq!(length_to_end.name, "Length to End");
+
+        let (replaced, conflicts) = Dictionary::merge(&[
+            ("standard", &standard, Override::Replace),
+            ("local", &local, Override::Replace),
+        ]);
+        let length_to_end = replaced.lookup(Tag::new(0x0008, 0x0001)).unwrap();
+        assert!(length_to_end.comment.is_none());
+        assert!(length_to_end.name.is_empty());
+        assert!(conflicts.is_empty());
+    }
+}
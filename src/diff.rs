@@ -0,0 +1,448 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use registry::Registry;
+use serde_json::Value;
+use DataElement;
+use UID;
+
+/// The changes between two editions of the data dictionary as returned by
+/// `diff`.
+#[derive(Clone, Debug, Default)]
+pub struct Changelog {
+    /// Elements only present in the new edition.
+    pub added_elements: Vec<DataElement>,
+
+    /// Elements only present in the old edition.
+    pub removed_elements: Vec<DataElement>,
+
+    /// Elements present in both editions which are retired in the new, but
+    /// not in the old edition (as of the new edition).
+    pub retired_elements: Vec<DataElement>,
+
+    /// Changes of name, keyword, VR or VM of elements present in both
+    /// editions.
+    pub changed_elements: Vec<Change>,
+
+    /// UIDs only present in the new edition.
+    pub added_uids: Vec<UID>,
+
+    /// UIDs only present in the old edition.
+    pub removed_uids: Vec<UID>,
+
+    /// UIDs present in both editions which are retired in the new, but not in
+    /// the old edition (as of the new edition).
+    pub retired_uids: Vec<UID>,
+
+    /// Changes of name or kind of UIDs present in both editions.
+    pub changed_uids: Vec<Change>,
+}
+
+/// A change of a single field of an element or UID between two editions.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Change {
+    /// The tag of the changed element (e.g. "(0008,0001)") or the value of the
+    /// changed UID (e.g. "1.2.840.10008.1.1").
+    pub key: String,
+
+    /// The name of the changed field (e.g. "VR").
+    pub field: &'static str,
+
+    /// The value of the field in the old edition.
+    pub old: String,
+
+    /// The value of the field in the new edition.
+    pub new: String,
+}
+
+/// Returns the changes from the `old` to the `new` edition of the registries,
+/// e.g. as parsed from two `Parser` instances via `Registry::from_parser`.
+///
+/// Elements are matched by tag and UIDs by value. Keywords are compared
+/// without zero-width spaces, so that changes of only their placement are not
+/// reported. Retiring an element or UID is reported in the respective
+/// "retired" list and not as change of its name. A UID which is renamed
+/// while being retired is reported in both lists.
+pub fn diff(old: &Registry, new: &Registry) -> Changelog {
+    let mut changelog = Changelog::default();
+
+    let old_elements = elements_by_tag(old);
+    let new_elements = elements_by_tag(new);
+    for (tag, new_element) in &new_elements {
+        let old_element = match old_elements.get(tag) {
+            Some(element) => element,
+            None => {
+                changelog.added_elements.push((*new_element).clone());
+                continue;
+            }
+        };
+
+        if new_element.is_retired() && !old_element.is_retired() {
+            changelog.retired_elements.push((*new_element).clone());
+        }
+
+        let fields = [
+            ("Name", &old_element.name, &new_element.name),
+            ("Keyword", &old_element.keyword, &new_element.keyword),
+            ("VR", &old_element.vr, &new_element.vr),
+            ("VM", &old_element.vm, &new_element.vm),
+        ];
+        for &(field, old_value, new_value) in &fields {
+            if normalize(old_value) != normalize(new_value) {
+                changelog.changed_elements.push(Change {
+                    key: tag.clone(),
+                    field,
+                    old: old_value.clone(),
+                    new: new_value.clone(),
+                });
+            }
+        }
+    }
+    changelog.removed_elements = old_elements
+        .iter()
+        .filter(|&(tag, _)| !new_elements.contains_key(tag))
+        .map(|(_, element)| (*element).clone())
+        .collect();
+
+    let old_uids: BTreeMap<&str, &UID> = old.uids.iter().map(|u| (u.value.as_str(), u)).collect();
+    let new_uids: BTreeMap<&str, &UID> = new.uids.iter().map(|u| (u.value.as_str(), u)).collect();
+    for (value, new_uid) in &new_uids {
+        let old_uid = match old_uids.get(value) {
+            Some(uid) => uid,
+            None => {
+                changelog.added_uids.push((*new_uid).clone());
+                continue;
+            }
+        };
+
+        if new_uid.is_retired() && !old_uid.is_retired() {
+            changelog.retired_uids.push((*new_uid).clone());
+        }
+
+        // the normalized name has no "(Retired)", so that retiring is no change
+        if old_uid.normalized_name != new_uid.normalized_name {
+            changelog.changed_uids.push(Change {
+                key: value.to_string(),
+                field: "Name",
+                old: old_uid.full_name.clone(),
+                new: new_uid.full_name.clone(),
+            });
+        }
+
+        if old_uid.kind != new_uid.kind {
+            changelog.changed_uids.push(Change {
+                key: value.to_string(),
+                field: "Kind",
+                old: format!("{:?}", old_uid.kind),
+                new: format!("{:?}", new_uid.kind),
+            });
+        }
+    }
+    changelog.removed_uids = old_uids
+        .iter()
+        .filter(|&(value, _)| !new_uids.contains_key(value))
+        .map(|(_, uid)| (*uid).clone())
+        .collect();
+
+    changelog
+}
+
+impl Changelog {
+    /// Returns whether there are no changes at all.
+    pub fn is_empty(&self) -> bool {
+        self.added_elements.is_empty()
+            && self.removed_elements.is_empty()
+            && self.retired_elements.is_empty()
+            && self.changed_elements.is_empty()
+            && self.added_uids.is_empty()
+            && self.removed_uids.is_empty()
+            && self.retired_uids.is_empty()
+            && self.changed_uids.is_empty()
+    }
+
+    /// Renders the changelog as Markdown document. Sections without entries
+    /// are omitted.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("# Data Dictionary Changes\n");
+        if self.is_empty() {
+            markdown.push_str("\nNo changes.\n");
+            return markdown;
+        }
+
+        let element_lines = |elements: &[DataElement]| -> Vec<String> {
+            elements
+                .iter()
+                .map(|e| {
+                    format!(
+                        "{} {} ({}) {} {}",
+                        e.tag,
+                        e.name,
+                        normalize(&e.keyword),
+                        e.vr,
+                        e.vm
+                    )
+                })
+                .collect()
+        };
+        let uid_lines = |uids: &[UID]| -> Vec<String> {
+            uids.iter()
+                .map(|u| format!("{} {}", u.value, u.full_name))
+                .collect()
+        };
+        let change_lines = |changes: &[Change]| -> Vec<String> {
+            changes
+                .iter()
+                .map(|c| format!("{} {}: \"{}\" -> \"{}\"", c.key, c.field, c.old, c.new))
+                .collect()
+        };
+
+        let sections = [
+            ("Added Data Elements", element_lines(&self.added_elements)),
+            (
+                "Removed Data Elements",
+                element_lines(&self.removed_elements),
+            ),
+            (
+                "Retired Data Elements",
+                element_lines(&self.retired_elements),
+            ),
+            (
+                "Changed Data Elements",
+                change_lines(&self.changed_elements),
+            ),
+            ("Added UIDs", uid_lines(&self.added_uids)),
+            ("Removed UIDs", uid_lines(&self.removed_uids)),
+            ("Retired UIDs", uid_lines(&self.retired_uids)),
+            ("Changed UIDs", change_lines(&self.changed_uids)),
+        ];
+        for &(title, ref lines) in &sections {
+            if lines.is_empty() {
+                continue;
+            }
+
+            let _ = write!(markdown, "\n## {}\n\n", title);
+            for line in lines {
+                let _ = writeln!(markdown, "* {}", line);
+            }
+        }
+
+        markdown
+    }
+
+    /// Renders the changelog as JSON object with the keys "added_elements",
+    /// "removed_elements", "retired_elements", "changed_elements",
+    /// "added_uids", "removed_uids", "retired_uids" and "changed_uids".
+    pub fn to_json(&self) -> String {
+        let element_json = |elements: &[DataElement]| -> Vec<Value> {
+            elements
+                .iter()
+                .map(|e| {
+                    json!({
+                        "tag": e.tag,
+                        "name": e.name,
+                        "keyword": normalize(&e.keyword),
+                        "vr": e.vr,
+                        "vm": e.vm,
+                    })
+                })
+                .collect()
+        };
+        let uid_json = |uids: &[UID]| -> Vec<Value> {
+            uids.iter()
+                .map(|u| {
+                    json!({
+                        "value": u.value,
+                        "name": u.full_name,
+                        "kind": format!("{:?}", u.kind),
+                    })
+                })
+                .collect()
+        };
+        let change_json = |changes: &[Change]| -> Vec<Value> {
+            changes
+                .iter()
+                .map(|c| {
+                    json!({
+                        "key": c.key,
+                        "field": c.field,
+                        "old": c.old,
+                        "new": c.new,
+                    })
+                })
+                .collect()
+        };
+
+        json!({
+            "added_elements": element_json(&self.added_elements),
+            "removed_elements": element_json(&self.removed_elements),
+            "retired_elements": element_json(&self.retired_elements),
+            "changed_elements": change_json(&self.changed_elements),
+            "added_uids": uid_json(&self.added_uids),
+            "removed_uids": uid_json(&self.removed_uids),
+            "retired_uids": uid_json(&self.retired_uids),
+            "changed_uids": change_json(&self.changed_uids),
+        })
+        .to_string()
+    }
+}
+
+fn elements_by_tag(registry: &Registry) -> BTreeMap<String, &DataElement> {
+    registry
+        .elements()
+        .map(|e| (e.normalized_tag(), e))
+        .collect()
+}
+
+fn normalize(value: &str) -> String {
+    value.replace("\u{200b}", "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+    use Kind;
+
+    fn element(tag: &str, name: &str, vr: &str, comment: Option<&str>) -> DataElement {
+        DataElement {
+            tag: tag.to_owned(),
+            name: name.to_owned(),
+            keyword: name.replace(" ", "\u{200b}"),
+            vr: vr.to_owned(),
+            vm: "1".to_owned(),
+            comment: comment.map(str::to_owned),
+        }
+    }
+
+    fn uid(value: &str, full_name: &str) -> UID {
+        UID {
+            value: value.to_owned(),
+            full_name: full_name.to_owned(),
+            normalized_name: full_name.replace(" (Retired)", ""),
+            kind: Kind::SopClass,
+        }
+    }
+
+    fn editions() -> (Registry, Registry) {
+        let old = Registry {
+            data_elements: vec![
+                element("(0008,0001)", "Length to End", "UL", None),
+                element("(0008,0005)", "Specific Character Set", "CS", None),
+                element("(0018,0061)", "Unused", "DS", None),
+            ],
+            uids: vec![
+                uid("1.2.840.10008.1.1", "Verification SOP Class"),
+                uid(
+                    "1.2.840.10008.3.1.2.1.1",
+                    "Detached Patient Management SOP Class",
+                ),
+            ],
+            ..Default::default()
+        };
+        let new = Registry {
+            data_elements: vec![
+                element("(0008,0001)", "Length to End", "UL", Some("RET")),
+                element("(0008,0005)", "Specific Character Set", "LO", None),
+                element("(0008,0006)", "Language Code Sequence", "SQ", None),
+            ],
+            uids: vec![
+                uid("1.2.840.10008.1.1", "Verification SOP Class"),
+                uid(
+                    "1.2.840.10008.3.1.2.1.1",
+                    "Detached Patient Management SOP Class (Retired)",
+                ),
+                uid("1.2.840.10008.1.20", "Papyrus 3 Implicit VR Little Endian"),
+            ],
+            ..Default::default()
+        };
+        (old, new)
+    }
+
+    #[test]
+    fn diff_reports_added_removed_retired_and_changed_entries() {
+        let (old, new) = editions();
+        let changelog = diff(&old, &new);
+
+        assert_eq!(changelog.added_elements.len(), 1);
+        assert_eq!(changelog.added_elements[0].tag, "(0008,0006)");
+        assert_eq!(changelog.removed_elements.len(), 1);
+        assert_eq!(changelog.removed_elements[0].tag, "(0018,0061)");
+        assert_eq!(changelog.retired_elements.len(), 1);
+        assert_eq!(changelog.retired_elements[0].tag, "(0008,0001)");
+        assert_eq!(
+            changelog.changed_elements,
+            vec![Change {
+                key: "(0008,0005)".to_owned(),
+                field: "VR",
+                old: "CS".to_owned(),
+                new: "LO".to_owned(),
+            }]
+        );
+
+        assert_eq!(changelog.added_uids.len(), 1);
+        assert!(changelog.removed_uids.is_empty());
+        assert_eq!(changelog.retired_uids.len(), 1);
+        assert!(changelog.changed_uids.is_empty());
+    }
+
+    #[test]
+    fn diff_reports_uid_renamed_while_retired() {
+        let old = Registry {
+            uids: vec![uid(
+                "1.2.840.10008.5.1.1.9",
+                "Basic Print Image Box SOP Class",
+            )],
+            ..Default::default()
+        };
+        let new = Registry {
+            uids: vec![uid(
+                "1.2.840.10008.5.1.1.9",
+                "Basic Grayscale Print Management Meta SOP Class (Retired)",
+            )],
+            ..Default::default()
+        };
+
+        let changelog = diff(&old, &new);
+        assert_eq!(changelog.retired_uids, new.uids);
+        assert_eq!(
+            changelog.changed_uids,
+            vec![Change {
+                key: "1.2.840.10008.5.1.1.9".to_owned(),
+                field: "Name",
+                old: "Basic Print Image Box SOP Class".to_owned(),
+                new: "Basic Grayscale Print Management Meta SOP Class (Retired)".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn renderers_include_changes() {
+        let (old, new) = editions();
+        let changelog = diff(&old, &new);
+
+        let markdown = changelog.to_markdown();
+        assert!(
+            markdown.contains("## Changed Data Elements\n\n* (0008,0005) VR: \"CS\" -> \"LO\"\n")
+        );
+        assert!(!markdown.contains("## Removed UIDs"));
+
+        let json: Value = serde_json::from_str(&changelog.to_json()).unwrap();
+        assert_eq!(json["added_elements"][0]["tag"], "(0008,0006)");
+        assert_eq!(
+            json["changed_elements"],
+            json!([{"key": "(0008,0005)", "field": "VR", "old": "CS", "new": "LO"}])
+        );
+        assert_eq!(json["removed_uids"], json!([]));
+    }
+
+    #[test]
+    fn diff_of_identical_editions_is_empty() {
+        let (old, _) = editions();
+        let changelog = diff(&old, &old);
+        assert!(changelog.is_empty());
+        assert_eq!(
+            changelog.to_markdown(),
+            "# Data Dictionary Changes\n\nNo changes.\n"
+        );
+    }
+}
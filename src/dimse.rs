@@ -0,0 +1,208 @@
+use std::error::Error;
+use std::path::Path;
+
+use document::StandardDocument;
+use status::{self, StatusCodeRegistry};
+use table::{Cell, Table};
+use DataElement;
+
+/// A parser for the definitions of the DICOM message exchange in part 7
+/// "Message Exchange" of the DICOM standard, e.g. the command elements of
+/// group 0000 which are not part of the data dictionary in part 6 or the
+/// status codes of DIMSE responses.
+pub struct DimseParser {
+    /// The DICOM standard part 7 xml file once read.
+    document: StandardDocument,
+}
+
+impl DimseParser {
+    /// Creates a new `DimseParser` instance with a downloaded version of the
+    /// current part 7 of the DICOM standard.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Downloading part07.xml fails
+    /// * Reading the downloaded part07.xml fails
+    pub fn new() -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            document: StandardDocument::new(7)?,
+        })
+    }
+
+    /// Creates a new `DimseParser` instance using the part07.xml given as
+    /// `file_path`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Opening the file at `file_path` fails
+    /// * Reading the file at `file_path` fails
+    pub fn with_part7_file(file_path: &Path) -> Result<Self, ::std::io::Error> {
+        Ok(Self {
+            document: StandardDocument::with_file(7, file_path)?,
+        })
+    }
+
+    /// Creates a new `DimseParser` instance given the full `contents` of a
+    /// part07.xml file.
+    pub fn with_part7_file_contents(contents: String) -> Self {
+        Self {
+            document: StandardDocument::with_file_contents(7, contents),
+        }
+    }
+
+    /// Returns the underlying part 7 document.
+    pub fn document(&self) -> &StandardDocument {
+        &self.document
+    }
+
+    /// Returns all command elements defined in the "Command Fields" and
+    /// "Retired Command Fields" tables of Annex E "Command Dictionary". The
+    /// message field is returned as the name of the element and retired
+    /// elements have the comment "RET", just like the elements of part 6.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Parsing of the part07.xml fails
+    ///   * The "Command Fields" table cannot be found
+    ///   * The table has no "Tag" column
+    pub fn parse_command_element_registry(&self) -> Result<Vec<DataElement>, Box<dyn Error>> {
+        let mut elements = Self::command_elements(&self.document.table("Command Fields")?, false)?;
+
+        // older editions do not list the retired command fields separately
+        if self
+            .document
+            .tables()?
+            .iter()
+            .any(|t| t.caption == "Retired Command Fields")
+        {
+            elements.extend(Self::command_elements(
+                &self.document.table("Retired Command Fields")?,
+                true,
+            )?);
+        }
+
+        Ok(elements)
+    }
+
+    /// Returns all status codes defined in the status tables of part 7, i.e.
+    /// all tables listing a "Service Status" along with status codes. Codes
+    /// defined in tables of a specific DIMSE service (e.g. "C-STORE") are
+    /// returned with that service, all others as general statuses.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if parsing of the part07.xml fails.
+    pub fn parse_status_code_registry(&self) -> Result<StatusCodeRegistry, Box<dyn Error>> {
+        Ok(StatusCodeRegistry::from_codes(status::parse_status_codes(
+            &self.document,
+        )?))
+    }
+
+    fn command_elements(table: &Table, retired: bool) -> Result<Vec<DataElement>, Box<dyn Error>> {
+        let tag_column = match table.column("Tag") {
+            Some(column) => column,
+            None => {
+                return Err(From::from(format!(
+                    "Unable to find column 'Tag' in table '{}'.",
+                    table.info.caption
+                )))
+            }
+        };
+        let columns = [
+            table.column("Message Field"),
+            table.column("Keyword"),
+            table.column("VR"),
+            table.column("VM"),
+        ];
+
+        Ok(table
+            .rows
+            .iter()
+            .filter(|row| row.len() > tag_column)
+            .map(|row| DataElement {
+                tag: row[tag_column].text.clone(),
+                name: cell_text(row, columns[0]),
+                keyword: cell_text(row, columns[1]),
+                vr: cell_text(row, columns[2]),
+                vm: cell_text(row, columns[3]),
+                comment: if retired {
+                    Some("RET".to_owned())
+                } else {
+                    None
+                },
+            })
+            .collect())
+    }
+}
+
+/// Returns the text of the cell in `column` of `row`, or an empty string if
+/// the table has no such column.
+fn cell_text(row: &[Cell], column: Option<usize>) -> String {
+    column
+        .and_then(|c| row.get(c))
+        .map(|cell| cell.text.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fixtures::fixture;
+    use {Dictionary, Registry, StatusCategory, Tag};
+
+    #[test]
+    fn parse_command_element_registry_includes_retired_fields() {
+        let elements = DimseParser::with_part7_file_contents(fixture(7).to_owned())
+            .parse_command_element_registry()
+            .unwrap();
+        assert_eq!(elements.len(), 6);
+
+        let command_field = &elements[2];
+        assert_eq!(command_field.tag, "(0000,0100)");
+        assert_eq!(command_field.name, "Command Field");
+        assert_eq!(command_field.keyword, "Command\u{200b}Field");
+        assert_eq!(command_field.vr, "US");
+        assert_eq!(command_field.vm, "1");
+        assert!(!command_field.is_retired());
+
+        assert_eq!(elements[5].tag, "(0000,5010)");
+        assert!(elements[5].is_retired());
+    }
+
+    #[test]
+    fn parse_status_code_registry_reads_general_and_service_statuses() {
+        let registry = DimseParser::with_part7_file_contents(fixture(7).to_owned())
+            .parse_status_code_registry()
+            .unwrap();
+        assert_eq!(registry.len(), 9);
+
+        let out_of_resources = registry.lookup(0xA702, Some("C-STORE")).unwrap();
+        assert_eq!(out_of_resources.to_string(), "A7xx");
+        assert_eq!(out_of_resources.meaning, "Refused: Out of Resources");
+        assert_eq!(out_of_resources.category, StatusCategory::Failure);
+
+        let processing_failure = registry.lookup(0x0110, Some("C-STORE")).unwrap();
+        assert_eq!(processing_failure.category, StatusCategory::Failure);
+        assert!(processing_failure.service.is_none());
+        assert!(registry.lookup(0xC123, Some("C-FIND")).is_none());
+    }
+
+    #[test]
+    fn command_elements_are_looked_up_along_with_part_6() {
+        let mut registry = Registry::default();
+        registry
+            .add_command_elements(&DimseParser::with_part7_file_contents(
+                fixture(7).to_owned(),
+            ))
+            .unwrap();
+        let dictionary = Dictionary::from_elements(registry.elements().cloned()).unwrap();
+        let status = dictionary.lookup(Tag::new(0x0000, 0x0900)).unwrap();
+        assert_eq!(status.name, "Status");
+    }
+}
@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use table::{self, Table, TableInfo};
+
+use reqwest;
+use xml::reader::{EventReader, XmlEvent};
+use xmltree;
+
+/// The name of the elements holding the text runs of an element that mixes
+/// text and child elements (see `parse_xml`).
+pub(crate) const TEXT_NODE: &str = "#text";
+
+/// A single part of the DICOM standard in its docbook xml format (e.g.
+/// part06.xml for part 6 "Data Dictionary").
+///
+/// All parts of the standard (e.g. PS3.3, PS3.4, PS3.5, PS3.6, PS3.7, PS3.15
+/// and PS3.16) are published with the same conventions, so that their tables
+/// can be accessed the same way.
+#[derive(Clone, Debug)]
+pub struct StandardDocument {
+    /// The number of the part (e.g. 6 for part 6 "Data Dictionary").
+    part: u8,
+
+    /// Holds the contents of the xml file once read.
+    content: String,
+
+    /// Holds the root element of the xml once parsed, so that the multi-
+    /// megabyte xml is only parsed once for all tables and sections.
+    root: OnceLock<xmltree::Element>,
+}
+
+impl StandardDocument {
+    /// Creates a new `StandardDocument` instance with a downloaded version of
+    /// the current `part` of the DICOM standard.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Downloading the xml file of the part fails
+    /// * Reading the downloaded xml file fails
+    pub fn new(part: u8) -> Result<Self, Box<dyn Error>> {
+        let mut response = reqwest::get(&Self::url(part))?;
+        Ok(Self::with_file_contents(
+            part,
+            Self::read_content(&mut response)?,
+        ))
+    }
+
+    /// Creates a new `StandardDocument` instance of `part` using the xml file
+    /// given as `file_path`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Opening the file at `file_path` fails
+    /// * Reading the file at `file_path` fails
+    pub fn with_file(part: u8, file_path: &Path) -> Result<Self, ::std::io::Error> {
+        let mut file = File::open(file_path)?;
+        Ok(Self::with_file_contents(
+            part,
+            Self::read_content(&mut file)?,
+        ))
+    }
+
+    /// Creates a new `StandardDocument` instance of `part` given the full
+    /// `contents` of its xml file.
+    pub fn with_file_contents(part: u8, contents: String) -> Self {
+        Self {
+            part,
+            content: contents,
+            root: OnceLock::new(),
+        }
+    }
+
+    /// Returns the URL the current version of `part` is downloaded from (e.g.
+    /// ".../part06/part06.xml" for part 6).
+    pub fn url(part: u8) -> String {
+        format!(
+            "http://dicom.nema.org/medical/dicom/current/source/docbook/part{:02}/part{:02}.xml",
+            part, part
+        )
+    }
+
+    /// Returns the number of the part (e.g. 6 for part 6 "Data Dictionary").
+    pub fn part(&self) -> u8 {
+        self.part
+    }
+
+    /// Returns the full contents of the xml file.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Returns information about all tables of the document (e.g. their
+    /// caption and the chapter containing them) in document order.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if parsing of the xml fails.
+    pub fn tables(&self) -> Result<Vec<TableInfo>, Box<dyn Error>> {
+        let root = self.root()?;
+        Ok(table::list_tables(root)
+            .into_iter()
+            .map(|(info, _)| info)
+            .collect())
+    }
+
+    /// Returns the table whose xml:id (e.g. "table_A-2") or caption (e.g.
+    /// "Well-known Frames of Reference") is `table_name`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Parsing of the xml fails
+    /// * No table with the given xml:id or caption exists
+    pub fn table(&self, table_name: &str) -> Result<Table, Box<dyn Error>> {
+        match table::find_table(self.root()?, table_name) {
+            Some(table) => Ok(table),
+            None => Err(From::from(format!(
+                "Unable to find table '{}' in part {}.",
+                table_name, self.part
+            ))),
+        }
+    }
+
+    /// Returns the text of the section whose xml:id (e.g. "sect_A.4"), label
+    /// (e.g. "A.4") or title is `section_name`, including the text of all of
+    /// its sub-sections and tables.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Parsing of the xml fails
+    /// * No section with the given xml:id, label or title exists
+    pub fn section_text(&self, section_name: &str) -> Result<String, Box<dyn Error>> {
+        let root = self.root()?;
+        match find_section(root, section_name) {
+            Some(section) => Ok(table::element_text(section)),
+            None => Err(From::from(format!(
+                "Unable to find section '{}' in part {}.",
+                section_name, self.part
+            ))),
+        }
+    }
+
+    /// Returns the root element of the parsed xml. The xml is parsed on the
+    /// first call only.
+    pub(crate) fn root(&self) -> Result<&xmltree::Element, Box<dyn Error>> {
+        if let Some(root) = self.root.get() {
+            return Ok(root);
+        }
+
+        let root = parse_xml(&self.content)?;
+        Ok(self.root.get_or_init(|| root))
+    }
+
+    fn read_content<R: Read>(reader: &mut R) -> Result<String, ::std::io::Error> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+        Ok(content)
+    }
+}
+
+/// Parses `content` into a tree of elements.
+///
+/// `xmltree::Element::parse` only keeps the last text run of an element, so
+/// that e.g. "2<superscript>32</superscript>-2" loses its leading "2". Here,
+/// elements mixing text and child elements keep all of their text runs in
+/// document order as child elements named `TEXT_NODE` instead. All other
+/// elements are the same as with `xmltree::Element::parse`.
+///
+/// # Errors
+///
+/// This function fails if the xml is malformed.
+pub(crate) fn parse_xml(content: &str) -> Result<xmltree::Element, Box<dyn Error>> {
+    let mut elements: Vec<xmltree::Element> = Vec::new();
+    for event in EventReader::new(content.as_bytes()) {
+        match event? {
+            XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace,
+            } => {
+                let mut element = xmltree::Element::new(&name.local_name);
+                element.prefix = name.prefix;
+                element.namespace = name.namespace;
+                if !namespace.is_essentially_empty() {
+                    element.namespaces = Some(namespace);
+                }
+                element.attributes = attributes
+                    .into_iter()
+                    .map(|attribute| (attribute.name.local_name, attribute.value))
+                    .collect();
+                elements.push(element);
+            }
+            XmlEvent::EndElement { .. } => {
+                let mut element = match elements.pop() {
+                    Some(element) => element,
+                    None => return Err(From::from("Found unexpected end of element.")),
+                };
+                merge_text_nodes(&mut element);
+                match elements.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Ok(element),
+                }
+            }
+            XmlEvent::Characters(text) | XmlEvent::CData(text) | XmlEvent::Whitespace(text) => {
+                if let Some(element) = elements.last_mut() {
+                    let mut text_node = xmltree::Element::new(TEXT_NODE);
+                    text_node.text = Some(text);
+                    element.children.push(text_node);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Err(From::from("Unable to find root element."))
+}
+
+/// Moves the text runs of `element` into its `text` unless it mixes text and
+/// child elements. Whitespace between child elements is dropped.
+fn merge_text_nodes(element: &mut xmltree::Element) {
+    let is_text = |child: &xmltree::Element| child.name == TEXT_NODE;
+    let has_text = element
+        .children
+        .iter()
+        .any(|child| is_text(child) && child.text.as_ref().is_some_and(|t| !t.trim().is_empty()));
+    let has_elements = element.children.iter().any(|child| !is_text(child));
+    if has_text && has_elements {
+        return;
+    }
+
+    if has_text {
+        element.text = Some(
+            element
+                .children
+                .iter()
+                .filter_map(|child| child.text.as_deref())
+                .collect(),
+        );
+    }
+    element.children.retain(|child| !is_text(child));
+}
+
+/// Returns all sections and chapters in `element` with an xml:id keyed by
+/// that id (e.g. "sect_C.7.3.1.1.1"), e.g. to resolve many links at once.
+pub(crate) fn sections_by_id(element: &xmltree::Element) -> HashMap<&str, &xmltree::Element> {
+    let mut sections = HashMap::new();
+    for child in &element.children {
+        if child.name == "section" || child.name == "chapter" {
+            if let Some(id) = child.attributes.get("id") {
+                sections.insert(id.as_str(), child);
+            }
+        }
+
+        sections.extend(sections_by_id(child));
+    }
+
+    sections
+}
+
+/// Returns the first section or chapter in `element` whose xml:id, label or
+/// title is `section_name`.
+pub(crate) fn find_section<'a>(
+    element: &'a xmltree::Element,
+    section_name: &str,
+) -> Option<&'a xmltree::Element> {
+    for child in &element.children {
+        if child.name == "section" || child.name == "chapter" {
+            let title = child.get_child("title").map(table::element_text);
+            if child.attributes.get("id").map(|id| id.as_str()) == Some(section_name)
+                || child.attributes.get("label").map(|l| l.as_str()) == Some(section_name)
+                || title.as_deref() == Some(section_name)
+            {
+                return Some(child);
+            }
+        }
+
+        if let Some(section) = find_section(child, section_name) {
+            return Some(section);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_xml_keeps_mixed_content_in_document_order() {
+        let root =
+            parse_xml("<para>2<superscript>32</superscript>-2 <emphasis>bytes</emphasis></para>")
+                .unwrap();
+        let children: Vec<(&str, Option<&str>)> = root
+            .children
+            .iter()
+            .map(|c| (c.name.as_str(), c.text.as_deref()))
+            .collect();
+        assert_eq!(
+            children,
+            vec![
+                (TEXT_NODE, Some("2")),
+                ("superscript", Some("32")),
+                (TEXT_NODE, Some("-2 ")),
+                ("emphasis", Some("bytes")),
+            ]
+        );
+        assert_eq!(root.text, None);
+
+        let root = parse_xml("<tr>\n  <td><para>A &amp; B</para></td>\n</tr>").unwrap();
+        assert_eq!(root.children.len(), 1);
+        assert_eq!(root.children[0].children[0].text.as_deref(), Some("A & B"));
+    }
+
+    #[test]
+    fn root_is_parsed_once() {
+        let document = StandardDocument::with_file_contents(
+            6,
+            "<book><chapter label=\"A\"/></book>".to_owned(),
+        );
+        let root = document.root().unwrap();
+        assert!(::std::ptr::eq(root, document.root().unwrap()));
+        assert_eq!(root.children[0].attributes["label"], "A");
+    }
+
+    #[test]
+    fn url_pads_part_number() {
+        assert_eq!(
+            StandardDocument::url(16),
+            "http://dicom.nema.org/medical/dicom/current/source/docbook/part16/part16.xml"
+        );
+        assert_eq!(
+            StandardDocument::url(3),
+            "http://dicom.nema.org/medical/dicom/current/source/docbook/part03/part03.xml"
+        );
+    }
+}
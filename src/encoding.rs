@@ -0,0 +1,279 @@
+use std::error::Error;
+use std::path::Path;
+
+use document::StandardDocument;
+use vr::{LengthUnit, VrDefinition, VR};
+
+/// A parser for the definitions of the encoding of data sets in part 5
+/// "Data Structures and Encoding" of the DICOM standard.
+pub struct EncodingParser {
+    /// The DICOM standard part 5 xml file once read.
+    document: StandardDocument,
+}
+
+impl EncodingParser {
+    /// Creates a new `EncodingParser` instance with a downloaded version of
+    /// the current part 5 of the DICOM standard.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Downloading part05.xml fails
+    /// * Reading the downloaded part05.xml fails
+    pub fn new() -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            document: StandardDocument::new(5)?,
+        })
+    }
+
+    /// Creates a new `EncodingParser` instance using the part05.xml given as
+    /// `file_path`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Opening the file at `file_path` fails
+    /// * Reading the file at `file_path` fails
+    pub fn with_part5_file(file_path: &Path) -> Result<Self, ::std::io::Error> {
+        Ok(Self {
+            document: StandardDocument::with_file(5, file_path)?,
+        })
+    }
+
+    /// Creates a new `EncodingParser` instance given the full `contents` of a
+    /// part05.xml file.
+    pub fn with_part5_file_contents(contents: String) -> Self {
+        Self {
+            document: StandardDocument::with_file_contents(5, contents),
+        }
+    }
+
+    /// Returns the underlying part 5 document.
+    pub fn document(&self) -> &StandardDocument {
+        &self.document
+    }
+
+    /// Returns the definitions of all VRs listed in the "DICOM Value
+    /// Representations" table.
+    ///
+    /// Whether a VR uses a 4 byte length with explicit VR transfer syntaxes
+    /// is taken from the caption of the table listing all VRs with a 2 byte
+    /// length ("Data Element with Explicit VR of AE, AS, ..."). The padding
+    /// character is taken from the definition of the VR if mentioned there,
+    /// otherwise character strings are padded with spaces and binary values
+    /// with NULL bytes as defined in section 6.2.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Parsing of the part05.xml fails
+    ///   * The "DICOM Value Representations" table cannot be found
+    ///   * The table listing the VRs with a 2 byte length cannot be found
+    ///   * A row of the table has less than four columns
+    ///   * A row of the table contains an unknown VR
+    pub fn parse_value_representations(&self) -> Result<Vec<VrDefinition>, Box<dyn Error>> {
+        let short_length_vrs = self.short_length_vrs()?;
+        let table = self.document.table("DICOM Value Representations")?;
+
+        let mut definitions = Vec::new();
+        for row in table.row_texts() {
+            if row.len() < 4 {
+                return Err(From::from(
+                    "Found unexpected number of 'td' elements in table 'DICOM Value Representations'",
+                ));
+            }
+
+            // the "VR Name" column holds the VR and its name as separate paragraphs
+            let mut vr_name = row[0].splitn(2, '\n');
+            let vr: VR = vr_name.next().unwrap_or_default().parse()?;
+            let name = vr_name.next().unwrap_or_default().trim().to_owned();
+
+            let (max_length, length_unit, fixed_length) = Self::parse_length(&row[3]);
+            let padding = Self::parse_padding(&row[1]).or_else(|| {
+                if row[3].starts_with("not applicable") {
+                    None
+                } else if row[2].starts_with("not applicable") {
+                    Some('\0')
+                } else {
+                    Some(' ')
+                }
+            });
+
+            definitions.push(VrDefinition {
+                vr,
+                name,
+                definition: row[1].clone(),
+                character_repertoire: row[2].clone(),
+                length_of_value: row[3].clone(),
+                max_length,
+                length_unit,
+                fixed_length,
+                padding,
+                has_4_byte_length: !short_length_vrs.contains(&vr),
+            });
+        }
+
+        Ok(definitions)
+    }
+
+    /// Returns the UIDs of all transfer syntaxes mentioned in the section
+    /// "Transfer Syntaxes For Encapsulation of Encoded Pixel Data" of Annex A,
+    /// which use encapsulated pixel data.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Parsing of the part05.xml fails
+    /// * The section cannot be found
+    pub fn parse_encapsulated_transfer_syntax_uids(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let text = self
+            .document
+            .section_text("Transfer Syntaxes For Encapsulation of Encoded Pixel Data")?
+            .replace("\u{200b}", "");
+
+        let mut uids: Vec<String> = Vec::new();
+        for word in text.split(|c: char| c.is_whitespace() || c == '"' || c == ',') {
+            let word = word.trim_end_matches('.');
+            if word.starts_with("1.2.840.10008.1.2.")
+                && word.chars().all(|c| c.is_ascii_digit() || c == '.')
+                && !uids.iter().any(|uid| uid == word)
+            {
+                uids.push(word.to_owned());
+            }
+        }
+
+        Ok(uids)
+    }
+
+    /// Returns the VRs with a 2 byte length field in explicit VR transfer
+    /// syntaxes as listed in the caption "Data Element with Explicit VR of AE,
+    /// AS, ... and US".
+    fn short_length_vrs(&self) -> Result<Vec<VR>, Box<dyn Error>> {
+        let prefix = "Data Element with Explicit VR of ";
+        let caption = match self
+            .document
+            .tables()?
+            .into_iter()
+            .find(|t| t.caption.starts_with(prefix))
+        {
+            Some(info) => info.caption,
+            None => return Err(From::from(format!("Unable to find table '{}...'.", prefix))),
+        };
+
+        caption[prefix.len()..]
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|word| !word.is_empty() && *word != "and")
+            .map(|word| word.parse())
+            .collect()
+    }
+
+    /// Parses a "Length of Value" (e.g. "16 bytes maximum", "8 bytes fixed"
+    /// or "2^32-2 bytes maximum") into maximum length, unit and whether the
+    /// length is fixed.
+    fn parse_length(length_of_value: &str) -> (Option<u32>, LengthUnit, bool) {
+        let unit = if length_of_value.contains("char") {
+            LengthUnit::Characters
+        } else {
+            LengthUnit::Bytes
+        };
+        let fixed = length_of_value.contains("fixed");
+
+        // lengths like "2^32-2" of UT are only limited by the length field
+        let max_length = length_of_value
+            .split_whitespace()
+            .next()
+            .and_then(Self::parse_number)
+            .filter(|&length| length < u64::from(u32::MAX - 1))
+            .map(|length| length as u32);
+        (max_length, unit, fixed)
+    }
+
+    /// Parses a number (e.g. "64") or a power with an optional subtrahend
+    /// (e.g. "2^32-2").
+    fn parse_number(number: &str) -> Option<u64> {
+        let (power, subtrahend) = match number.find('-') {
+            Some(index) => (&number[..index], number[index + 1..].parse().ok()?),
+            None => (number, 0),
+        };
+        let value = match power.find('^') {
+            Some(index) => {
+                let base: u64 = power[..index].parse().ok()?;
+                base.checked_pow(power[index + 1..].parse().ok()?)?
+            }
+            None => power.parse().ok()?,
+        };
+        value.checked_sub(subtrahend)
+    }
+
+    /// Returns the padding character mentioned in the `definition` of a VR.
+    fn parse_padding(definition: &str) -> Option<char> {
+        definition
+            .split(". ")
+            .filter(|sentence| sentence.contains("pad"))
+            .filter_map(|sentence| {
+                if sentence.contains("NULL") || sentence.contains("00H") {
+                    Some('\0')
+                } else if sentence.contains("SPACE") || sentence.contains("20H") {
+                    Some(' ')
+                } else {
+                    None
+                }
+            })
+            .next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fixtures::fixture;
+
+    #[test]
+    fn parse_value_representations_derives_length_rules() {
+        let definitions = EncodingParser::with_part5_file_contents(fixture(5).to_owned())
+            .parse_value_representations()
+            .unwrap();
+        assert_eq!(definitions.len(), 8);
+
+        let ae = &definitions[0];
+        assert_eq!(ae.vr, VR::AE);
+        assert_eq!(ae.name, "Application Entity");
+        assert_eq!(ae.max_length, Some(16));
+        assert_eq!(ae.length_unit, LengthUnit::Bytes);
+        assert!(!ae.fixed_length);
+        assert!(!ae.has_4_byte_length);
+
+        let da = &definitions[1];
+        assert_eq!(da.max_length, Some(8));
+        assert!(da.fixed_length);
+
+        assert_eq!(EncodingParser::parse_number("2^16-1"), Some(65535));
+        assert_eq!(EncodingParser::parse_number("maximum"), None);
+
+        let lo = &definitions[2];
+        assert_eq!(lo.max_length, Some(64));
+        assert_eq!(lo.length_unit, LengthUnit::Characters);
+
+        let ut = &definitions[7];
+        assert_eq!(ut.vr, VR::UT);
+        assert_eq!(ut.length_of_value, "2^32-2 bytes maximum\nSee Note 2.");
+        assert_eq!(ut.max_length, None);
+        assert!(ut.has_4_byte_length);
+    }
+
+    #[test]
+    fn parse_value_representations_derives_padding() {
+        let definitions = EncodingParser::with_part5_file_contents(fixture(5).to_owned())
+            .parse_value_representations()
+            .unwrap();
+        let padding = |vr| definitions.iter().find(|d| d.vr == vr).unwrap().padding;
+        assert_eq!(padding(VR::AE), Some(' '));
+        assert_eq!(padding(VR::UI), Some('\0'));
+        assert_eq!(padding(VR::OB), Some('\0'));
+        assert_eq!(padding(VR::SQ), None);
+    }
+}
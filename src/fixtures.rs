@@ -0,0 +1,29 @@
+//! The fixtures in tests/fixtures shared by the unit tests of all modules.
+
+/// Returns the contents of the fixture of `part` of the DICOM standard (e.g.
+/// "part03.xml" for 3). For part 6, this is the 2020a edition, see
+/// `part6_fixture` for the others.
+pub(crate) fn fixture(part: u8) -> &'static str {
+    match part {
+        3 => include_str!("../tests/fixtures/part03.xml"),
+        4 => include_str!("../tests/fixtures/part04.xml"),
+        5 => include_str!("../tests/fixtures/part05.xml"),
+        6 => part6_fixture("2020a"),
+        7 => include_str!("../tests/fixtures/part07.xml"),
+        15 => include_str!("../tests/fixtures/part15.xml"),
+        16 => include_str!("../tests/fixtures/part16.xml"),
+        _ => panic!("No fixture of part {}", part),
+    }
+}
+
+/// Returns the contents of the part 6 fixture with the given `suffix`, i.e.
+/// its edition (e.g. "2019a") or "storage" for the one listing storage SOP
+/// classes only.
+pub(crate) fn part6_fixture(suffix: &str) -> &'static str {
+    match suffix {
+        "2019a" => include_str!("../tests/fixtures/part06_2019a.xml"),
+        "2020a" => include_str!("../tests/fixtures/part06_2020a.xml"),
+        "storage" => include_str!("../tests/fixtures/part06_storage.xml"),
+        _ => panic!("No part 6 fixture '{}'", suffix),
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use registry::Registry;
+use tag::TagRange;
+use Parser;
+
+/// The history of a single data element or UID across multiple editions of
+/// the standard.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Timeline {
+    /// The first edition containing the element or UID.
+    pub first_seen: String,
+
+    /// The first edition in which the element or UID is retired. If it was
+    /// already retired when first seen, this is the same as `first_seen`.
+    pub retired_in: Option<String>,
+
+    /// The first edition no longer containing the element or UID, if it was
+    /// removed and has not been re-added since.
+    pub removed_in: Option<String>,
+}
+
+impl Timeline {
+    fn new(edition: &str, retired: bool) -> Self {
+        Timeline {
+            first_seen: edition.to_owned(),
+            retired_in: if retired {
+                Some(edition.to_owned())
+            } else {
+                None
+            },
+            removed_in: None,
+        }
+    }
+
+    fn update(&mut self, edition: &str, retired: bool) {
+        self.removed_in = None;
+        if retired && self.retired_in.is_none() {
+            self.retired_in = Some(edition.to_owned());
+        }
+    }
+}
+
+/// The history of all data elements and UIDs across multiple editions of the
+/// standard (e.g. "2019a", "2020a", ...).
+///
+/// Editions have to be added in chronological order, starting with the
+/// oldest one.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    editions: Vec<String>,
+    elements: BTreeMap<String, Timeline>,
+    uids: BTreeMap<String, Timeline>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new `History` from `editions` given as pairs of edition name
+    /// and parser, ordered from the oldest to the newest edition.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if parsing the registries of any edition fails.
+    pub fn from_parsers(editions: &[(&str, &Parser)]) -> Result<Self, Box<dyn Error>> {
+        let mut history = History::new();
+        for &(edition, parser) in editions {
+            history.add_edition(edition, &Registry::from_parser(parser)?);
+        }
+
+        Ok(history)
+    }
+
+    /// Adds the registries of `edition`, which has to be newer than all
+    /// editions added before.
+    pub fn add_edition(&mut self, edition: &str, registry: &Registry) {
+        let elements: BTreeMap<String, bool> = registry
+            .elements()
+            .map(|e| (e.normalized_tag(), e.is_retired()))
+            .collect();
+        let uids: BTreeMap<String, bool> = registry
+            .uids
+            .iter()
+            .map(|u| (u.value.clone(), u.is_retired()))
+            .collect();
+
+        Self::add_entries(&mut self.elements, edition, &elements);
+        Self::add_entries(&mut self.uids, edition, &uids);
+        self.editions.push(edition.to_owned());
+    }
+
+    /// Returns the names of all added editions, from oldest to newest.
+    pub fn editions(&self) -> &[String] {
+        &self.editions
+    }
+
+    /// Returns the timeline of the element with the given `tag` (e.g.
+    /// "(0008,0001)" or "(60xx,3000)").
+    pub fn element(&self, tag: &str) -> Option<&Timeline> {
+        match tag.parse::<TagRange>() {
+            Ok(range) => self.elements.get(&range.to_string()),
+            Err(_) => self.elements.get(tag),
+        }
+    }
+
+    /// Returns the timeline of the UID with the given `value`.
+    pub fn uid(&self, value: &str) -> Option<&Timeline> {
+        self.uids.get(value)
+    }
+
+    /// Returns the tag and timeline of all elements ordered by tag.
+    pub fn elements(&self) -> impl Iterator<Item = (&str, &Timeline)> {
+        self.elements.iter().map(|(tag, t)| (tag.as_str(), t))
+    }
+
+    /// Returns the value and timeline of all UIDs ordered by value.
+    pub fn uids(&self) -> impl Iterator<Item = (&str, &Timeline)> {
+        self.uids.iter().map(|(value, t)| (value.as_str(), t))
+    }
+
+    fn add_entries(
+        timelines: &mut BTreeMap<String, Timeline>,
+        edition: &str,
+        entries: &BTreeMap<String, bool>,
+    ) {
+        for (key, &retired) in entries {
+            timelines
+                .entry(key.clone())
+                .and_modify(|t| t.update(edition, retired))
+                .or_insert_with(|| Timeline::new(edition, retired));
+        }
+
+        for (key, timeline) in timelines.iter_mut() {
+            if timeline.removed_in.is_none() && !entries.contains_key(key) {
+                timeline.removed_in = Some(edition.to_owned());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fixtures::part6_fixture;
+
+    fn history() -> History {
+        let edition_2019a = Parser::with_part6_file_contents(part6_fixture("2019a").to_owned());
+        let edition_2020a = Parser::with_part6_file_contents(part6_fixture("2020a").to_owned());
+        History::from_parsers(&[("2019a", &edition_2019a), ("2020a", &edition_2020a)]).unwrap()
+    }
+
+    #[test]
+    fn history_tracks_first_seen_and_retired_in_editions() {
+        let history = history();
+        assert_eq!(history.editions(), &["2019a", "2020a"]);
+
+        let length_to_end = history.element("(0008,0001)").unwrap();
+        assert_eq!(length_to_end.first_seen, "2019a");
+        assert_eq!(length_to_end.retired_in, Some("2020a".to_owned()));
+        assert!(length_to_end.removed_in.is_none());
+
+        let language_code_sequence = history.element("(0008,0006)").unwrap();
+        assert_eq!(language_code_sequence.first_seen, "2020a");
+        assert!(language_code_sequence.retired_in.is_none());
+
+        let already_retired = history.element("(0018,0061)").unwrap();
+        assert_eq!(already_retired.retired_in, Some("2019a".to_owned()));
+        assert_eq!(already_retired.removed_in, Some("2020a".to_owned()));
+
+        assert!(history.element("(60XX,3000)").is_some());
+        assert!(history.element("(0002,0010)").is_some());
+    }
+
+    #[test]
+    fn history_tracks_uids() {
+        let history = history();
+
+        let explicit_big_endian = history.uid("1.2.840.10008.1.2.2").unwrap();
+        assert_eq!(explicit_big_endian.first_seen, "2019a");
+        assert_eq!(explicit_big_endian.retired_in, Some("2020a".to_owned()));
+
+        let htj2k = history.uid("1.2.840.10008.1.2.4.201").unwrap();
+        assert_eq!(htj2k.first_seen, "2020a");
+        assert_eq!(history.uids().count(), 4);
+    }
+}
@@ -0,0 +1,466 @@
+use std::error::Error;
+use std::path::Path;
+
+use character_set::{CharacterSet, CharacterSetRegistry, CodeElement};
+use document::{self, StandardDocument};
+use iod::{Attribute, Iod, IodModule, IodRegistry, Module, ModuleItem};
+use table::{self, Table};
+use value_list::{self, ValueListRegistry};
+
+/// A parser for the Information Object Definitions in part 3 "Information
+/// Object Definitions" of the DICOM standard.
+pub struct InformationObjectParser {
+    /// The DICOM standard part 3 xml file once read.
+    document: StandardDocument,
+}
+
+impl InformationObjectParser {
+    /// Creates a new `InformationObjectParser` instance with a downloaded
+    /// version of the current part 3 of the DICOM standard.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Downloading part03.xml fails
+    /// * Reading the downloaded part03.xml fails
+    pub fn new() -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            document: StandardDocument::new(3)?,
+        })
+    }
+
+    /// Creates a new `InformationObjectParser` instance using the part03.xml
+    /// given as `file_path`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Opening the file at `file_path` fails
+    /// * Reading the file at `file_path` fails
+    pub fn with_part3_file(file_path: &Path) -> Result<Self, ::std::io::Error> {
+        Ok(Self {
+            document: StandardDocument::with_file(3, file_path)?,
+        })
+    }
+
+    /// Creates a new `InformationObjectParser` instance given the full
+    /// `contents` of a part03.xml file.
+    pub fn with_part3_file_contents(contents: String) -> Self {
+        Self {
+            document: StandardDocument::with_file_contents(3, contents),
+        }
+    }
+
+    /// Returns the underlying part 3 document.
+    pub fn document(&self) -> &StandardDocument {
+        &self.document
+    }
+
+    /// Returns all IODs, modules and macros of part 3.
+    ///
+    /// IODs are read from all tables with a "Module" and a "Usage" column
+    /// (e.g. "CT Image IOD Modules"), modules and macros from all tables with
+    /// an "Attribute Name" and a "Tag" column (e.g. "Patient Module
+    /// Attributes"). Nested attributes (prefixed with ">") are added to the
+    /// items of the preceding sequence, "Include" rows are kept as references
+    /// to the included table.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Parsing of the part03.xml fails
+    /// * The usage of a module of an IOD is unknown
+    pub fn parse_iod_registry(&self) -> Result<IodRegistry, Box<dyn Error>> {
+        let root = self.document.root()?;
+
+        let mut iods = Vec::new();
+        let mut modules = Vec::new();
+        for (info, element) in table::list_tables(root) {
+            let table = Table::from_element(element, info);
+            if table.column("Module").is_some() && table.column("Usage").is_some() {
+                iods.push(Self::iod(&table)?);
+            } else if table.column("Attribute Name").is_some() && table.column("Tag").is_some() {
+                modules.push(Self::module(&table));
+            }
+        }
+
+        Ok(IodRegistry::from_parts(iods, modules))
+    }
+
+    /// Returns the enumerated values and defined terms listed in the
+    /// descriptions of the attributes of all modules and macros or in the
+    /// "Attribute Descriptions" sections linked from there (e.g. C.7.3.1.1.1
+    /// "Modality"), along with the table they apply in.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if parsing of the part03.xml fails.
+    pub fn parse_value_list_registry(&self) -> Result<ValueListRegistry, Box<dyn Error>> {
+        let root = self.document.root()?;
+        let sections = document::sections_by_id(root);
+
+        let mut lists = Vec::new();
+        for (info, element) in table::list_tables(root) {
+            let table = Table::from_element(element, info);
+            if table.column("Attribute Name").is_some() && table.column("Tag").is_some() {
+                lists.extend(value_list::value_lists(
+                    element,
+                    &table.info.caption,
+                    &table.info.id,
+                    &sections,
+                ));
+            }
+        }
+
+        Ok(ValueListRegistry::from_lists(lists))
+    }
+
+    /// Returns the defined terms of Specific Character Set (0008,0005) listed
+    /// in the "Defined Terms for ... Character Sets ..." tables of section
+    /// C.12.1.1.2. Rows spanned by the same defined term are combined into
+    /// the code elements of a single character set.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if parsing of the part03.xml fails.
+    pub fn parse_character_set_registry(&self) -> Result<CharacterSetRegistry, Box<dyn Error>> {
+        let root = self.document.root()?;
+
+        let mut character_sets: Vec<CharacterSet> = Vec::new();
+        for (info, element) in table::list_tables(root) {
+            let caption = &info.caption;
+            if !caption.starts_with("Defined Terms for") || !caption.contains("Character Sets") {
+                continue;
+            }
+            let code_extensions = caption.contains("with Code Extensions");
+            let multi_byte = caption.contains("Multi-Byte");
+
+            let table = Table::from_element(element, info);
+            let term_column = match table.column("Defined Term") {
+                Some(column) => column,
+                None => continue,
+            };
+            let description_column = table.column("Character Set Description");
+            let escape_column = table.column("ESC Sequence");
+            let registration_column = table.column("ISO Registration Number");
+            let code_element_column = table.column("Code Element");
+            let character_set_column = table.column("Character Set");
+
+            for row in &table.rows {
+                let text = |column: Option<usize>| {
+                    column
+                        .and_then(|c| row.get(c))
+                        .map(|cell| cell.text.trim().to_owned())
+                        .unwrap_or_default()
+                };
+
+                // the default repertoire has no defined term
+                let mut defined_term = text(Some(term_column));
+                if defined_term == "none" {
+                    defined_term.clear();
+                }
+
+                let code_element = CodeElement {
+                    iso_registration: text(registration_column),
+                    escape_sequence: Some(text(escape_column)).filter(|s| !s.is_empty()),
+                    code_element: text(code_element_column),
+                    character_set: text(character_set_column),
+                };
+
+                let description = text(description_column);
+                match character_sets.last_mut() {
+                    Some(last)
+                        if last.defined_term == defined_term && last.description == description =>
+                    {
+                        last.elements.push(code_element)
+                    }
+                    _ => character_sets.push(CharacterSet {
+                        defined_term,
+                        description,
+                        code_extensions,
+                        multi_byte,
+                        elements: vec![code_element],
+                    }),
+                }
+            }
+        }
+
+        Ok(CharacterSetRegistry::from_character_sets(character_sets))
+    }
+
+    fn iod(table: &Table) -> Result<Iod, Box<dyn Error>> {
+        let ie_column = table.column("IE");
+        let module_column = table.column("Module").unwrap_or_default();
+        let reference_column = table.column("Reference");
+        let usage_column = table.column("Usage").unwrap_or_default();
+
+        let mut modules = Vec::new();
+        for row in &table.rows {
+            let (module, usage) = match (row.get(module_column), row.get(usage_column)) {
+                (Some(module), Some(usage)) => (module, usage),
+                _ => continue,
+            };
+
+            modules.push(IodModule {
+                information_entity: ie_column
+                    .and_then(|c| row.get(c))
+                    .map(|cell| cell.text.clone())
+                    .unwrap_or_default(),
+                name: module.text.clone(),
+                reference: reference_column
+                    .and_then(|c| row.get(c))
+                    .and_then(|cell| cell.links.first().cloned()),
+                usage: usage.text.parse()?,
+            });
+        }
+
+        let caption = &table.info.caption;
+        Ok(Iod {
+            name: caption
+                .trim_end_matches(" Modules")
+                .trim_end_matches(" IOD")
+                .to_owned(),
+            table_id: table.info.id.clone(),
+            modules,
+        })
+    }
+
+    fn module(table: &Table) -> Module {
+        let name_column = table.column("Attribute Name").unwrap_or_default();
+        let tag_column = table.column("Tag").unwrap_or_default();
+        let type_column = table.column("Type");
+        let description_column = table
+            .column("Attribute Description")
+            .or_else(|| table.column("Description"));
+
+        let mut items = Vec::new();
+        for row in &table.rows {
+            let name_cell = match row.get(name_column) {
+                Some(cell) => cell,
+                None => continue,
+            };
+            let depth = name_cell.text.chars().take_while(|&c| c == '>').count();
+            let name = name_cell.text[depth..].trim();
+
+            let item = if name.starts_with("Include") {
+                match name_cell.links.first() {
+                    Some(target) => ModuleItem::Include(target.clone()),
+                    None => continue,
+                }
+            } else {
+                ModuleItem::Attribute(Attribute {
+                    name: name.to_owned(),
+                    tag: row
+                        .get(tag_column)
+                        .map(|cell| cell.text.clone())
+                        .unwrap_or_default(),
+                    attribute_type: type_column
+                        .and_then(|c| row.get(c))
+                        .and_then(|cell| cell.text.parse().ok()),
+                    description: description_column
+                        .and_then(|c| row.get(c))
+                        .map(|cell| cell.text.clone())
+                        .unwrap_or_default(),
+                    items: Vec::new(),
+                })
+            };
+
+            Self::insert_item(&mut items, depth, item);
+        }
+
+        Module {
+            caption: table.info.caption.clone(),
+            table_id: table.info.id.clone(),
+            section_id: table.info.section_id.clone(),
+            items,
+        }
+    }
+
+    /// Adds `item` to the items of the last sequence at nesting level `depth`
+    /// of `items`, or to the innermost existing level if there is none.
+    fn insert_item(items: &mut Vec<ModuleItem>, depth: usize, item: ModuleItem) {
+        if depth > 0 {
+            if let Some(&mut ModuleItem::Attribute(ref mut sequence)) = items.last_mut() {
+                return Self::insert_item(&mut sequence.items, depth - 1, item);
+            }
+        }
+
+        items.push(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fixtures::fixture;
+    use iod::{AttributeType, Usage};
+    use value_list::ValueListKind;
+    use {DataElement, Dictionary, Tag};
+
+    #[test]
+    fn parse_iod_registry_reads_modules_with_usage() {
+        let registry = InformationObjectParser::with_part3_file_contents(fixture(3).to_owned())
+            .parse_iod_registry()
+            .unwrap();
+        let ct = registry.iod("CT Image").unwrap();
+        assert_eq!(ct.table_id, "table_A.3-1");
+        assert_eq!(ct.modules.len(), 4);
+        assert_eq!(ct.modules[1].information_entity, "Patient");
+        assert_eq!(ct.modules[1].name, "Clinical Trial Subject");
+        assert_eq!(ct.modules[1].usage, Usage::UserOption);
+        assert_eq!(
+            ct.modules[3].usage,
+            Usage::Conditional("Required if contrast media was used in this image".to_owned())
+        );
+
+        let patient = registry.module_of(&ct.modules[0]).unwrap();
+        assert_eq!(patient.table_id, "table_C.7-1");
+        assert!(registry.module_of(&ct.modules[1]).is_none());
+        assert!(registry.module("table_10-11").unwrap().is_macro());
+    }
+
+    #[test]
+    fn parse_iod_registry_nests_sequences_and_expands_includes() {
+        let registry = InformationObjectParser::with_part3_file_contents(fixture(3).to_owned())
+            .parse_iod_registry()
+            .unwrap();
+        let patient = registry.module("sect_C.7.1.1").unwrap();
+        assert_eq!(patient.items.len(), 5);
+        match patient.items[3] {
+            ModuleItem::Attribute(ref sequence) => {
+                assert_eq!(sequence.name, "Referenced Patient Sequence");
+                assert_eq!(sequence.attribute_type, Some(AttributeType::Type3));
+                assert_eq!(
+                    sequence.items,
+                    vec![ModuleItem::Include("table_10-11".to_owned())]
+                );
+            }
+            ref item => panic!("unexpected item {:?}", item),
+        }
+
+        let attributes: Vec<(usize, &str)> = registry
+            .attributes(patient)
+            .into_iter()
+            .map(|(depth, a)| (depth, a.tag.as_str()))
+            .collect();
+        assert_eq!(
+            attributes,
+            vec![
+                (0, "(0010,0010)"),
+                (0, "(0010,0020)"),
+                (0, "(0010,0040)"),
+                (0, "(0008,1120)"),
+                (1, "(0008,1150)"),
+                (1, "(0008,1155)"),
+                (0, "(0010,1002)"),
+                (1, "(0010,0020)"),
+                (1, "(0010,0022)"),
+            ]
+        );
+
+        let ct = registry.iod("CT Image").unwrap();
+        assert_eq!(registry.iod_attributes(ct).len(), 12);
+    }
+
+    #[test]
+    fn attributes_are_linked_to_data_elements_by_tag() {
+        let registry = InformationObjectParser::with_part3_file_contents(fixture(3).to_owned())
+            .parse_iod_registry()
+            .unwrap();
+        let dictionary = Dictionary::from_elements(vec![DataElement {
+            tag: "(0010,0010)".to_owned(),
+            name: "Patient's Name".to_owned(),
+            keyword: "Patient\u{200b}Name".to_owned(),
+            vr: "PN".to_owned(),
+            vm: "1".to_owned(),
+            comment: None,
+        }])
+        .unwrap();
+
+        let attributes = registry.attributes(registry.module("table_C.7-1").unwrap());
+        assert_eq!(attributes[0].1.data_element(&dictionary).unwrap().vr, "PN");
+        assert!(attributes[1].1.data_element(&dictionary).is_none());
+    }
+
+    #[test]
+    fn parse_value_list_registry_keeps_module_context() {
+        let registry = InformationObjectParser::with_part3_file_contents(fixture(3).to_owned())
+            .parse_value_list_registry()
+            .unwrap();
+        assert_eq!(registry.len(), 3);
+
+        let sex = registry
+            .lookup_in(Tag::new(0x0010, 0x0040), "table_C.7-1")
+            .unwrap();
+        assert_eq!(sex.kind, ValueListKind::EnumeratedValues);
+        assert_eq!(sex.module, "Patient Module Attributes");
+        let values: Vec<&str> = sex.terms.iter().map(|t| t.value.as_str()).collect();
+        assert_eq!(values, vec!["M", "F", "O"]);
+        assert_eq!(sex.terms[2].meaning, "other");
+
+        let type_of_patient_id = &registry.lookup(Tag::new(0x0010, 0x0022))[0];
+        assert_eq!(type_of_patient_id.kind, ValueListKind::DefinedTerms);
+        assert!(type_of_patient_id.contains("RFID"));
+        assert!(registry.lookup(Tag::new(0x0010, 0x0010)).is_empty());
+    }
+
+    #[test]
+    fn parse_value_list_registry_reads_linked_attribute_descriptions() {
+        let registry = InformationObjectParser::with_part3_file_contents(fixture(3).to_owned())
+            .parse_value_list_registry()
+            .unwrap();
+
+        let modality = registry.lookup(Tag::new(0x0008, 0x0060));
+        assert_eq!(modality.len(), 1);
+        assert_eq!(modality[0].kind, ValueListKind::DefinedTerms);
+        assert_eq!(modality[0].module, "General Series Module Attributes");
+        assert_eq!(modality[0].table_id, "table_C.7-5a");
+        let values: Vec<&str> = modality[0].terms.iter().map(|t| t.value.as_str()).collect();
+        assert_eq!(values, vec!["CT", "MR", "SR"]);
+        assert_eq!(modality[0].terms[1].meaning, "Magnetic Resonance");
+
+        // the linked section describes another attribute
+        assert!(registry.lookup(Tag::new(0x0020, 0x000E)).is_empty());
+    }
+
+    #[test]
+    fn parse_character_set_registry_combines_spanned_rows() {
+        let registry = InformationObjectParser::with_part3_file_contents(fixture(3).to_owned())
+            .parse_character_set_registry()
+            .unwrap();
+        assert_eq!(registry.len(), 7);
+
+        let default = registry.get("").unwrap();
+        assert_eq!(default.description, "Default repertoire");
+        assert!(!default.code_extensions);
+        assert_eq!(default.encoding_name(), Some("US-ASCII"));
+
+        let latin1 = registry.get("ISO 2022 IR 100").unwrap();
+        assert!(latin1.code_extensions);
+        assert!(!latin1.multi_byte);
+        assert_eq!(latin1.elements.len(), 2);
+        assert_eq!(latin1.elements[0].iso_registration, "ISO-IR 100");
+        assert_eq!(latin1.elements[0].code_element, "G1");
+        assert_eq!(
+            latin1.elements[1].escape_bytes(),
+            Some(vec![0x1B, 0x28, 0x42])
+        );
+        assert_eq!(latin1.encoding_name(), Some("ISO-8859-1"));
+
+        let japanese = registry.by_escape_sequence(&[0x1B, 0x24, 0x42]).unwrap().0;
+        assert_eq!(japanese.defined_term, "ISO 2022 IR 87");
+        assert!(japanese.multi_byte);
+
+        let utf8 = registry.get("ISO_IR 192").unwrap();
+        assert!(utf8.multi_byte);
+        assert!(!utf8.code_extensions);
+        assert_eq!(utf8.encoding_name(), Some("UTF-8"));
+
+        let sets = registry.for_attribute_value("\\ISO 2022 IR 87").unwrap();
+        assert_eq!(sets.len(), 2);
+        assert!(registry.for_attribute_value("ISO_IR 999").is_none());
+    }
+}
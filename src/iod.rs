@@ -0,0 +1,325 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use dictionary::Dictionary;
+use tag::{Tag, TagRange};
+use DataElement;
+
+/// The usage of a module within an IOD.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Usage {
+    /// The module is mandatory ("M").
+    Mandatory,
+
+    /// The module is required under the given condition ("C").
+    Conditional(String),
+
+    /// The module is optional ("U").
+    UserOption,
+}
+
+impl FromStr for Usage {
+    type Err = Box<dyn Error>;
+
+    /// Parses the "Usage" column of an IOD module table (e.g. "M" or "C -
+    /// Required if ...").
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s.chars().next() {
+            Some('M') => Ok(Usage::Mandatory),
+            Some('U') => Ok(Usage::UserOption),
+            Some('C') => Ok(Usage::Conditional(
+                s[1..]
+                    .trim_start_matches(|c: char| c == '-' || c.is_whitespace())
+                    .to_owned(),
+            )),
+            _ => Err(From::from(format!("Unknown module usage '{}'", s))),
+        }
+    }
+}
+
+/// The type of an attribute within a module, defining whether it is required
+/// and whether it may be empty.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum AttributeType {
+    Type1,
+    Type1C,
+    Type2,
+    Type2C,
+    Type3,
+}
+
+impl fmt::Display for AttributeType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            AttributeType::Type1 => "1",
+            AttributeType::Type1C => "1C",
+            AttributeType::Type2 => "2",
+            AttributeType::Type2C => "2C",
+            AttributeType::Type3 => "3",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for AttributeType {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1" => Ok(AttributeType::Type1),
+            "1C" => Ok(AttributeType::Type1C),
+            "2" => Ok(AttributeType::Type2),
+            "2C" => Ok(AttributeType::Type2C),
+            "3" => Ok(AttributeType::Type3),
+            _ => Err(From::from(format!("Unknown attribute type '{}'", s))),
+        }
+    }
+}
+
+/// A module used by an IOD as listed in its module table.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IodModule {
+    /// The information entity the module belongs to (e.g. "Patient").
+    pub information_entity: String,
+
+    /// The name of the module (e.g. "Patient").
+    pub name: String,
+
+    /// The xml:id of the section defining the module (e.g. "sect_C.7.1.1").
+    pub reference: Option<String>,
+
+    /// The usage of the module within the IOD.
+    pub usage: Usage,
+}
+
+/// An Information Object Definition as defined by an IOD module table of
+/// part 3 (e.g. "CT Image IOD Modules").
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Iod {
+    /// The name of the IOD (e.g. "CT Image").
+    pub name: String,
+
+    /// The xml:id of the module table (e.g. "table_A.3-1").
+    pub table_id: String,
+
+    /// The modules of the IOD in the order of the table.
+    pub modules: Vec<IodModule>,
+}
+
+/// An attribute of a module or macro, including the attributes nested in it
+/// if it is a sequence.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attribute {
+    /// The name of the attribute (e.g. "Patient's Name").
+    pub name: String,
+
+    /// The tag of the attribute as given in the table (e.g. "(0010,0010)").
+    pub tag: String,
+
+    /// The type of the attribute, `None` if the table has no "Type" column
+    /// or the type is unknown.
+    pub attribute_type: Option<AttributeType>,
+
+    /// The description of the attribute.
+    pub description: String,
+
+    /// The attributes and includes nested in the items of the sequence.
+    pub items: Vec<ModuleItem>,
+}
+
+impl Attribute {
+    /// Returns the typed form of `tag`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if `tag` is not in the format "(gggg,eeee)".
+    pub fn tag_range(&self) -> Result<TagRange, Box<dyn Error>> {
+        self.tag.parse()
+    }
+
+    /// Returns the data element of the attribute in `dictionary`.
+    pub fn data_element<'a>(&self, dictionary: &'a Dictionary) -> Option<&'a DataElement> {
+        let range = self.tag_range().ok()?;
+        if range.is_range() {
+            dictionary.get(&range)
+        } else {
+            dictionary.lookup(Tag::new(range.group, range.element))
+        }
+    }
+}
+
+/// An entry of a module or macro table.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ModuleItem {
+    /// A single attribute.
+    Attribute(Attribute),
+
+    /// The attributes of another table (usually a macro) given by its xml:id
+    /// (e.g. "table_10-11").
+    Include(String),
+}
+
+/// A module or macro as defined by an attribute table of part 3 (e.g.
+/// "Patient Module Attributes").
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Module {
+    /// The caption of the table (e.g. "Patient Module Attributes").
+    pub caption: String,
+
+    /// The xml:id of the table (e.g. "table_C.7-1").
+    pub table_id: String,
+
+    /// The xml:id of the section containing the table (e.g. "sect_C.7.1.1").
+    pub section_id: Option<String>,
+
+    /// The top level attributes and includes of the table.
+    pub items: Vec<ModuleItem>,
+}
+
+impl Module {
+    /// Returns whether the table defines a macro instead of a module.
+    pub fn is_macro(&self) -> bool {
+        self.caption.contains("Macro")
+    }
+}
+
+/// All IODs, modules and macros of part 3.
+#[derive(Clone, Debug, Default)]
+pub struct IodRegistry {
+    iods: Vec<Iod>,
+    modules: BTreeMap<String, Module>,
+}
+
+impl IodRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new `IodRegistry` from `iods` and `modules`, which also
+    /// contain all macros.
+    pub fn from_parts(iods: Vec<Iod>, modules: Vec<Module>) -> Self {
+        IodRegistry {
+            iods,
+            modules: modules
+                .into_iter()
+                .map(|module| (module.table_id.clone(), module))
+                .collect(),
+        }
+    }
+
+    /// Returns the IOD with the given `name` (e.g. "CT Image").
+    pub fn iod(&self, name: &str) -> Option<&Iod> {
+        self.iods.iter().find(|iod| iod.name == name)
+    }
+
+    /// Returns all IODs in the order of the standard.
+    pub fn iods(&self) -> &[Iod] {
+        &self.iods
+    }
+
+    /// Returns the module or macro whose table has the xml:id `table_id` or
+    /// is contained in the section with the xml:id `table_id`.
+    pub fn module(&self, table_id: &str) -> Option<&Module> {
+        self.modules.get(table_id).or_else(|| {
+            self.modules
+                .values()
+                .find(|module| module.section_id.as_deref() == Some(table_id))
+        })
+    }
+
+    /// Returns the definition of a module used by an IOD.
+    pub fn module_of(&self, iod_module: &IodModule) -> Option<&Module> {
+        iod_module
+            .reference
+            .as_ref()
+            .and_then(|reference| self.module(reference))
+    }
+
+    /// Returns all modules and macros ordered by the xml:id of their table.
+    pub fn modules(&self) -> impl Iterator<Item = &Module> {
+        self.modules.values()
+    }
+
+    /// Returns all attributes of `module` together with their nesting level
+    /// (0 for top level attributes, 1 for attributes within items of a top
+    /// level sequence, ...) with all includes replaced by the included
+    /// attributes. Includes of unknown tables are skipped.
+    pub fn attributes<'a>(&'a self, module: &'a Module) -> Vec<(usize, &'a Attribute)> {
+        let mut attributes = Vec::new();
+        self.collect_attributes(
+            &module.items,
+            0,
+            &mut vec![&module.table_id],
+            &mut attributes,
+        );
+        attributes
+    }
+
+    /// Returns all attributes of all modules of `iod` along with the name of
+    /// their module, see `attributes`.
+    pub fn iod_attributes<'a>(&'a self, iod: &'a Iod) -> Vec<(&'a str, usize, &'a Attribute)> {
+        iod.modules
+            .iter()
+            .filter_map(|iod_module| self.module_of(iod_module).map(|m| (iod_module, m)))
+            .flat_map(|(iod_module, module)| {
+                self.attributes(module)
+                    .into_iter()
+                    .map(move |(depth, a)| (iod_module.name.as_str(), depth, a))
+            })
+            .collect()
+    }
+
+    fn collect_attributes<'a>(
+        &'a self,
+        items: &'a [ModuleItem],
+        depth: usize,
+        visited: &mut Vec<&'a str>,
+        attributes: &mut Vec<(usize, &'a Attribute)>,
+    ) {
+        for item in items {
+            match *item {
+                ModuleItem::Attribute(ref attribute) => {
+                    attributes.push((depth, attribute));
+                    self.collect_attributes(&attribute.items, depth + 1, visited, attributes);
+                }
+                ModuleItem::Include(ref table_id) => {
+                    // guard against macros (indirectly) including themselves
+                    if visited.contains(&table_id.as_str()) {
+                        continue;
+                    }
+
+                    if let Some(module) = self.modules.get(table_id) {
+                        visited.push(&module.table_id);
+                        self.collect_attributes(&module.items, depth, visited, attributes);
+                        visited.pop();
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usage_and_attribute_type_parse_table_values() {
+        assert_eq!("M".parse::<Usage>().unwrap(), Usage::Mandatory);
+        assert_eq!(
+            "C - Required if contrast media was used"
+                .parse::<Usage>()
+                .unwrap(),
+            Usage::Conditional("Required if contrast media was used".to_owned())
+        );
+        assert_eq!("U".parse::<Usage>().unwrap(), Usage::UserOption);
+        assert_eq!(
+            "2C".parse::<AttributeType>().unwrap(),
+            AttributeType::Type2C
+        );
+        assert!("4".parse::<AttributeType>().is_err());
+    }
+}
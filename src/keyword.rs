@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use DataElement;
+
+/// A case style to render a keyword in as an identifier.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Case {
+    /// E.g. "SOPInstanceUID", i.e. the keyword as given in the standard.
+    Pascal,
+
+    /// E.g. "sopInstanceUID".
+    Camel,
+
+    /// E.g. "sop_instance_uid".
+    Snake,
+
+    /// E.g. "SOP_INSTANCE_UID".
+    ScreamingSnake,
+}
+
+/// The keyword of a data element (e.g. "SOP\u{200b}Instance\u{200b}UID")
+/// split into its words.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Keyword {
+    segments: Vec<String>,
+}
+
+impl Keyword {
+    /// Splits `keyword` into its words.
+    ///
+    /// Keywords of the standard are split at their zero-width spaces, where
+    /// multiple zero-width spaces count as one. Keywords without zero-width
+    /// spaces (e.g. read from a dicom.dic) are split before every uppercase
+    /// letter following a lowercase one, before the last letter of a run of
+    /// uppercase letters followed by a lowercase one (e.g. "SOP" and
+    /// "Instance") and before digits following a lowercase letter (e.g. "X",
+    /// "Ray" and "3D").
+    pub fn new(keyword: &str) -> Self {
+        let keyword = keyword.trim();
+        let segments = if keyword.contains('\u{200b}') {
+            keyword
+                .split('\u{200b}')
+                .filter(|segment| !segment.is_empty())
+                .map(str::to_owned)
+                .collect()
+        } else {
+            Self::split_case(keyword)
+        };
+
+        Keyword { segments }
+    }
+
+    /// Returns the words of the keyword (e.g. ["SOP", "Instance", "UID"]).
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Renders the keyword as an identifier in the given `case`.
+    pub fn to_case(&self, case: Case) -> String {
+        match case {
+            Case::Pascal => self.segments.concat(),
+            Case::Camel => self
+                .segments
+                .iter()
+                .enumerate()
+                .map(|(index, segment)| {
+                    if index == 0 {
+                        segment.to_lowercase()
+                    } else {
+                        segment.clone()
+                    }
+                })
+                .collect(),
+            Case::Snake => self.join_segments(str::to_lowercase),
+            Case::ScreamingSnake => self.join_segments(str::to_uppercase),
+        }
+    }
+
+    pub fn to_pascal_case(&self) -> String {
+        self.to_case(Case::Pascal)
+    }
+
+    pub fn to_camel_case(&self) -> String {
+        self.to_case(Case::Camel)
+    }
+
+    pub fn to_snake_case(&self) -> String {
+        self.to_case(Case::Snake)
+    }
+
+    pub fn to_screaming_snake_case(&self) -> String {
+        self.to_case(Case::ScreamingSnake)
+    }
+
+    fn join_segments(&self, convert: fn(&str) -> String) -> String {
+        self.segments
+            .iter()
+            .map(|segment| convert(segment))
+            .collect::<Vec<_>>()
+            .join("_")
+    }
+
+    fn split_case(keyword: &str) -> Vec<String> {
+        let chars: Vec<char> = keyword.chars().collect();
+        let mut segments = Vec::new();
+        let mut segment = String::new();
+        for (index, &c) in chars.iter().enumerate() {
+            let previous = if index > 0 {
+                chars.get(index - 1)
+            } else {
+                None
+            };
+            let next = chars.get(index + 1);
+            let starts_word = match previous {
+                Some(p) if p.is_lowercase() => c.is_uppercase() || c.is_ascii_digit(),
+                Some(p) if p.is_uppercase() || p.is_ascii_digit() => {
+                    c.is_uppercase() && next.is_some_and(|n| n.is_lowercase())
+                }
+                _ => false,
+            };
+
+            if starts_word && !segment.is_empty() {
+                segments.push(segment);
+                segment = String::new();
+            }
+            segment.push(c);
+        }
+        if !segment.is_empty() {
+            segments.push(segment);
+        }
+
+        segments
+    }
+}
+
+impl fmt::Display for Keyword {
+    /// Formats the keyword without zero-width spaces (e.g. "PatientName").
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_pascal_case())
+    }
+}
+
+/// Returns every identifier generated from the keywords of `elements` in the
+/// given `case` that is shared by more than one element, along with these
+/// elements. Elements without keyword are skipped.
+pub fn duplicate_identifiers<'a, I>(elements: I, case: Case) -> Vec<(String, Vec<&'a DataElement>)>
+where
+    I: IntoIterator<Item = &'a DataElement>,
+{
+    let mut identifiers: BTreeMap<String, Vec<&DataElement>> = BTreeMap::new();
+    for element in elements {
+        let keyword = Keyword::new(&element.keyword);
+        if keyword.segments().is_empty() {
+            continue;
+        }
+
+        identifiers
+            .entry(keyword.to_case(case))
+            .or_default()
+            .push(element);
+    }
+
+    identifiers
+        .into_iter()
+        .filter(|(_, elements)| elements.len() > 1)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keywords_are_split_at_zero_width_spaces() {
+        let keyword = Keyword::new("SOP\u{200b}Instance\u{200b}\u{200b}UID");
+        assert_eq!(keyword.segments(), ["SOP", "Instance", "UID"]);
+        assert_eq!(keyword.to_pascal_case(), "SOPInstanceUID");
+        assert_eq!(keyword.to_camel_case(), "sopInstanceUID");
+        assert_eq!(keyword.to_snake_case(), "sop_instance_uid");
+        assert_eq!(keyword.to_screaming_snake_case(), "SOP_INSTANCE_UID");
+        assert_eq!(keyword.to_string(), "SOPInstanceUID");
+    }
+
+    #[test]
+    fn keywords_without_zero_width_spaces_are_split_by_case() {
+        assert_eq!(
+            Keyword::new("SOPInstanceUID").segments(),
+            ["SOP", "Instance", "UID"]
+        );
+        assert_eq!(
+            Keyword::new("XRay3DFrameTypeSequence").to_snake_case(),
+            "x_ray_3d_frame_type_sequence"
+        );
+        assert_eq!(Keyword::new("Bits8").segments(), ["Bits", "8"]);
+        assert!(Keyword::new("").segments().is_empty());
+    }
+
+    #[test]
+    fn duplicate_identifiers_are_reported_per_case() {
+        let element = |keyword: &str| DataElement {
+            keyword: keyword.to_owned(),
+            ..Default::default()
+        };
+        let elements = vec![
+            element("Patient\u{200b}Name"),
+            element("PatientName"),
+            element("Patient\u{200b}ID"),
+            element("PatientId"),
+            element(""),
+        ];
+
+        let pascal = duplicate_identifiers(&elements, Case::Pascal);
+        assert_eq!(pascal.len(), 1);
+        assert_eq!(pascal[0].0, "PatientName");
+
+        let snake = duplicate_identifiers(&elements, Case::Snake);
+        let identifiers: Vec<&str> = snake.iter().map(|(i, _)| i.as_str()).collect();
+        assert_eq!(identifiers, vec!["patient_id", "patient_name"]);
+    }
+}
@@ -4,6 +4,64 @@
 //! * "Registry of DICOM File Meta Elements"
 //! * "Registry of DICOM Directory Structuring Elements"
 //! * "Registry of DICOM Unique Identifiers (UIDs)"
+//! * "Well-known Frames of Reference"
+//! * "Context Group UID Values"
+//! * "Template UID Values"
+//!
+//! Any other table of the standard can be accessed via `Parser::tables` and
+//! `Parser::table`, which return its header and rows of cell text. The same
+//! is possible for every other part of the standard (e.g. part 3, 4 or 16) by
+//! using a `StandardDocument` for that part.
+//!
+//! The command elements of group 0000 defined in part 7 "Message Exchange" are
+//! parsed by `DimseParser` and can be added to a `Registry` to be looked up
+//! along with the elements of part 6. It also parses the DIMSE status codes
+//! into a `StatusCodeRegistry`, which looks up the meaning of a status like
+//! 0xA700.
+//!
+//! The rules of each VR (e.g. its length limits and padding) are parsed from
+//! part 5 "Data Structures and Encoding" by `EncodingParser`. The
+//! `TransferSyntaxRegistry` derives the encoding of each transfer syntax of
+//! the UID registry (e.g. explicit/implicit VR, byte order and compression),
+//! optionally supplemented by part 5.
+//!
+//! The storage SOP classes of part 4 "Service Class Specifications" are
+//! parsed by `ServiceClassParser` into a `StorageSopClassRegistry`, which can
+//! be cross-checked against the UID registry of part 6. The keys of each
+//! level of the Query/Retrieve Information Models are parsed into a
+//! `QueryRetrieveRegistry`.
+//!
+//! The IODs of part 3 "Information Object Definitions" with their modules and
+//! attributes are parsed by `InformationObjectParser` into an `IodRegistry`,
+//! the enumerated values and defined terms of the attributes into a
+//! `ValueListRegistry`. The defined terms of Specific Character Set are parsed
+//! into a `CharacterSetRegistry`, which maps them to escape sequences and
+//! encodings for decoding strings.
+//!
+//! The context groups of part 16 "Content Mapping Resource" are parsed by
+//! `ContentMappingParser` into a `ContextGroupRegistry`, which resolves the
+//! context group UIDs of part 6 to their coded concepts, and the codes of the
+//! "DICOM Controlled Terminology" into a `CodeRegistry`. The structured
+//! reporting templates are parsed into a `TemplateRegistry`, a tree of the
+//! content items of each template.
+//!
+//! The actions of the Attribute Confidentiality Profiles of part 15 "Security
+//! and System Management Profiles" are parsed by `SecurityProfileParser` into
+//! a `DeidentificationRegistry`, which looks up the action for a tag with the
+//! enabled profile options and can be cross-checked against part 6.
+//!
+//! Additionally, existing dictionaries in the formats of DCMTK ("dicom.dic")
+//! and pydicom ("_dicom_dict.py") can be read into the same data model by
+//! using `DcmtkParser` and `PydicomParser`. Private data elements can be
+//! loaded from text or JSON into a `PrivateDictionary` and resolved for
+//! concrete private tags.
+//! Multiple sources can be combined with `Dictionary::merge`, which reports
+//! every conflicting definition between them, and `Dictionary::implicit_vr`
+//! resolves the VR of any tag for decoding implicit VR data sets. Two
+//! editions of the standard can be compared with `diff`, which renders its
+//! result as Markdown or JSON, and the `History` of many editions shows when
+//! each data element and UID first appeared and when it was retired.
+//! Keywords can be rendered as identifiers in various cases with `Keyword`.
 //!
 //! # Examples
 //!
@@ -26,10 +84,8 @@
 //!     let mut buf_writer = BufWriter::new(file);
 //!     for data_element in data_elements {
 //!         let upper_case_keyword = data_element
-//!             .keyword
-//!             .replace("\u{200b}", "")
-//!             .replace("__", "_")
-//!             .to_uppercase();
+//!             .parsed_keyword()
+//!             .to_screaming_snake_case();
 //!
 //!         buf_writer.write_all(
 //!             format!(
@@ -44,13 +100,80 @@
 //! }
 //! ```
 
+pub mod character_set;
+pub mod code;
+pub mod content_mapping;
+pub mod context_group;
 pub mod data_element;
+pub mod dcmtk;
+pub mod deidentification;
+pub mod dictionary;
+pub mod diff;
+pub mod dimse;
+pub mod document;
+pub mod encoding;
+#[cfg(test)]
+mod fixtures;
+pub mod history;
+pub mod information_object;
+pub mod iod;
+pub mod keyword;
 pub mod parser;
+pub mod private_dictionary;
+pub mod pydicom;
+pub mod query_retrieve;
+pub mod registry;
+pub mod security;
+pub mod service_class;
+pub mod status;
+pub mod storage;
+pub mod table;
+pub mod tag;
+pub mod template;
+pub mod transfer_syntax;
 pub mod uid;
+pub mod value_list;
+pub mod vr;
 
+pub use character_set::{CharacterSet, CharacterSetRegistry, CodeElement};
+pub use code::{CodeRegistry, CodedConcept, DefinedCode};
+pub use content_mapping::ContentMappingParser;
+pub use context_group::{ContextGroup, ContextGroupMember, ContextGroupRegistry};
 pub use data_element::DataElement;
+pub use dcmtk::DcmtkParser;
+pub use deidentification::{
+    Action, ConfidentialityAttribute, DeidentificationRegistry, ProfileMismatch, ProfileOption,
+};
+pub use dictionary::{Conflict, Dictionary, Field, ImplicitVr, Override, VrContext};
+pub use diff::{diff, Changelog};
+pub use dimse::DimseParser;
+pub use document::StandardDocument;
+pub use encoding::EncodingParser;
+pub use history::{History, Timeline};
+pub use information_object::InformationObjectParser;
+pub use iod::{Attribute, AttributeType, Iod, IodModule, IodRegistry, Module, ModuleItem, Usage};
+pub use keyword::{Case, Keyword};
 pub use parser::Parser;
-pub use uid::{Kind, UID};
+pub use private_dictionary::{PrivateCreators, PrivateDataElement, PrivateDictionary};
+pub use pydicom::PydicomParser;
+pub use query_retrieve::{
+    InformationModel, KeyType, MatchingType, QueryKey, QueryLevel, QueryRetrieveRegistry,
+};
+pub use registry::Registry;
+pub use security::SecurityProfileParser;
+pub use service_class::ServiceClassParser;
+pub use status::{StatusCategory, StatusCode, StatusCodeRegistry};
+pub use storage::{SopClassMismatch, StorageCategory, StorageSopClass, StorageSopClassRegistry};
+pub use table::{Cell, Table, TableInfo};
+pub use tag::{Tag, TagClass, TagRange};
+pub use template::{RequirementType, Template, TemplateItem, TemplateRegistry, ValueType};
+pub use transfer_syntax::{ByteOrder, CompressionFamily, TransferSyntax, TransferSyntaxRegistry};
+pub use uid::{ContextGroupUID, FrameOfReference, Kind, TemplateUID, UID};
+pub use value_list::{Term, ValueList, ValueListKind, ValueListRegistry};
+pub use vr::{LengthUnit, VrDefinition, VR};
 
 extern crate reqwest;
+#[macro_use]
+extern crate serde_json;
+extern crate xml;
 extern crate xmltree;
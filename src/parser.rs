@@ -1,20 +1,20 @@
 use std::error::Error;
-use std::fs::File;
-use std::io::Read;
 use std::path::Path;
 
+use document::StandardDocument;
+use table::{self, Table, TableInfo};
+use uid::{ContextGroupUID, FrameOfReference, TemplateUID};
 use DataElement;
 use Kind;
 use UID;
 
-use reqwest;
 use xmltree;
 
 /// A parser for the data elements defined in various tables in the DICOM
 /// standard (part 6 "Data Dictionary").
 pub struct Parser {
-    /// Holds the contents of the DICOM standard part 6 xml file once read.
-    part6_content: String,
+    /// The DICOM standard part 6 xml file once read.
+    document: StandardDocument,
 }
 
 impl Parser {
@@ -29,7 +29,7 @@ impl Parser {
     /// * Reading the downloaded part6.xml fails
     pub fn new() -> Result<Self, Box<Error>> {
         Ok(Self {
-            part6_content: Self::download_part_6()?,
+            document: StandardDocument::new(6)?,
         })
     }
 
@@ -42,19 +42,24 @@ impl Parser {
     /// * Opening the file at `file_path` fails
     /// * Reading the file at `file_path` fails
     pub fn with_part6_file(file_path: &Path) -> Result<Self, ::std::io::Error> {
-        let mut file = File::open(file_path)?;
         Ok(Self {
-            part6_content: Self::read_content(&mut file)?,
+            document: StandardDocument::with_file(6, file_path)?,
         })
     }
 
     /// Creates a new `Parser` instance given the full `contents` of a part6.xml file.
     pub fn with_part6_file_contents(contents: String) -> Self {
         Self {
-            part6_content: contents,
+            document: StandardDocument::with_file_contents(6, contents),
         }
     }
 
+    /// Returns the underlying part 6 document, e.g. to access tables not
+    /// covered by any of the `parse_*` functions.
+    pub fn document(&self) -> &StandardDocument {
+        &self.document
+    }
+
     /// Returns all data elements defined in the "Registry of DICOM Data
     /// Elements" table of the DICOM standard.
     ///
@@ -125,8 +130,8 @@ impl Parser {
     ///   * The format of how values are stored in part6.xml has changed and this
     /// function is no longer able to parse it appropriately
     pub fn parse_unique_identifier_registry(&self) -> Result<Vec<UID>, Box<Error>> {
-        let root = xmltree::Element::parse(self.part6_content.as_bytes())?;
-        let chapter_a_table_body = match Self::find_chapter_table_body(&root, "A") {
+        let root = self.document.root()?;
+        let chapter_a_table_body = match Self::find_chapter_table_body(root, "A") {
             Some(element) => element,
             None => return Err(From::from("Unable to find chapter 'A' table body.")),
         };
@@ -136,31 +141,32 @@ impl Parser {
         // xml underneath chapter tbody is <tr><td><para></para></td><td>...</tr>
         for tr in &chapter_a_table_body.children {
             let mut uid = UID::new();
-            let mut counter = 0;
-            for td in &tr.children {
-                let mut para = &td.children[0];
-                assert!(para.name == "para");
-
-                if !para.children.is_empty() && &para.children[0].name == "emphasis" {
-                    // some text is italic and thus has an extra "emphasis" sub-element...
-                    para = &para.children[0];
-                }
-
-                let text = para.text.clone();
+            for (counter, text) in Self::row_cells(tr).into_iter().enumerate() {
+                let text = match text {
+                    Some(text) => text,
+                    // the "Part" column is empty for some UIDs
+                    None if counter == 3 => continue,
+                    None => {
+                        return Err(From::from(format!(
+                            "Found empty 'td' element in column {} of chapter 'A' table",
+                            counter
+                        )))
+                    }
+                };
 
                 match counter {
                     0 => {
-                        uid.value = text.unwrap();
+                        uid.value = text;
 
                         // values in "UID Value" column contain zero-width spaces...
                         // we'll trim them out
                         uid.value = uid.value.replace("\u{200b}", "");
                     }
                     1 => {
-                        uid.full_name = text.unwrap();
+                        uid.full_name = text;
                         uid.normalized_name = Self::normalize_uid_name(&uid.full_name);
                     }
-                    2 => match text.unwrap().as_ref() {
+                    2 => match text.as_ref() {
                         "Application Context Name" => uid.kind = Kind::ApplicationContextName,
                         "Application Hosting Model" => uid.kind = Kind::ApplicationHostingModel,
                         "Coding Scheme" => uid.kind = Kind::CodingScheme,
@@ -189,8 +195,6 @@ impl Parser {
                     3 => { /* "Part" column, which we ignore right now */ }
                     _ => return Err(From::from("Found unexpected number of 'td' elements")),
                 }
-
-                counter += 1;
             }
 
             uids.push(uid);
@@ -199,17 +203,101 @@ impl Parser {
         Ok(uids)
     }
 
-    fn download_part_6() -> Result<String, Box<Error>> {
-        let mut response = reqwest::get(
-            "http://dicom.nema.org/medical/dicom/current/source/docbook/part06/part06.xml",
-        )?;
+    /// Returns all frames of reference defined in the "Well-known Frames of
+    /// Reference" table of the DICOM standard.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Parsing of the part6.xml fails
+    ///   * The "Well-known Frames of Reference" table cannot be found
+    ///   * A row of the table has less than three columns
+    pub fn parse_well_known_frame_of_reference_registry(
+        &self,
+    ) -> Result<Vec<FrameOfReference>, Box<dyn Error>> {
+        let rows = self.parse_table_rows("Well-known Frames of Reference", 3)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| FrameOfReference {
+                value: row[0].replace("\u{200b}", ""),
+                name: row[1].clone(),
+                normative_reference: row[2].clone(),
+            })
+            .collect())
+    }
+
+    /// Returns all context group UIDs defined in the "Context Group UID
+    /// Values" table of the DICOM standard.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Parsing of the part6.xml fails
+    ///   * The "Context Group UID Values" table cannot be found
+    ///   * A row of the table has less than three columns
+    pub fn parse_context_group_uid_registry(&self) -> Result<Vec<ContextGroupUID>, Box<dyn Error>> {
+        let rows = self.parse_table_rows("Context Group UID Values", 3)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| ContextGroupUID {
+                value: row[0].replace("\u{200b}", ""),
+                context_identifier: row[1].clone(),
+                name: row[2].clone(),
+            })
+            .collect())
+    }
+
+    /// Returns all template UIDs defined in the "Template UID Values" table of
+    /// the DICOM standard.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Parsing of the part6.xml fails
+    ///   * The "Template UID Values" table cannot be found (e.g. in older
+    ///     editions of the standard)
+    ///   * A row of the table has less than three columns
+    pub fn parse_template_uid_registry(&self) -> Result<Vec<TemplateUID>, Box<dyn Error>> {
+        let rows = self.parse_table_rows("Template UID Values", 3)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| TemplateUID {
+                value: row[0].replace("\u{200b}", ""),
+                template_identifier: row[1].clone(),
+                name: row[2].clone(),
+            })
+            .collect())
+    }
+
+    /// Returns information about all tables of part 6 (e.g. their caption and
+    /// the chapter containing them) in document order.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if parsing of the part6.xml fails.
+    pub fn tables(&self) -> Result<Vec<TableInfo>, Box<dyn Error>> {
+        self.document.tables()
+    }
 
-        Self::read_content(&mut response).map_err(|e| e.into())
+    /// Returns the table whose xml:id (e.g. "table_A-2") or caption (e.g.
+    /// "Well-known Frames of Reference") is `table_name`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Parsing of the part6.xml fails
+    /// * No table with the given xml:id or caption exists
+    pub fn table(&self, table_name: &str) -> Result<Table, Box<dyn Error>> {
+        self.document.table(table_name)
     }
 
     fn parse_data_elements(&self, chapter_label: &str) -> Result<Vec<DataElement>, Box<Error>> {
-        let root = xmltree::Element::parse(self.part6_content.as_bytes())?;
-        let chapter_table_body = match Self::find_chapter_table_body(&root, chapter_label) {
+        let root = self.document.root()?;
+        let chapter_table_body = match Self::find_chapter_table_body(root, chapter_label) {
             Some(element) => element,
             None => {
                 return Err(From::from(format!(
@@ -224,42 +312,29 @@ impl Parser {
         // xml underneath chapter tbody is <tr><td><para></para></td><td>...</tr>
         for tr in &chapter_table_body.children {
             let mut data_element = DataElement::new();
-            let mut counter = 0;
-            for td in &tr.children {
-                let mut para = &td.children[0];
-                assert!(para.name == "para");
-
-                if !para.children.is_empty() && &para.children[0].name == "emphasis" {
-                    // some text is italic and thus has an extra "emphasis" sub-element...
-                    para = &para.children[0];
-                }
-
-                let text = para.text.clone();
-
+            for (counter, text) in Self::row_cells(tr).into_iter().enumerate() {
                 // name, keyword, vr and/or vm is empty for a handful of elements...
-                if text.is_none() && counter != 5 {
-                    counter += 1;
-                    continue;
-                }
+                let text = match text {
+                    Some(text) => text,
+                    None => continue,
+                };
 
                 match counter {
-                    0 => data_element.tag = text.unwrap(),
-                    1 => data_element.name = text.unwrap(),
-                    2 => data_element.keyword = text.unwrap(),
+                    0 => data_element.tag = text,
+                    1 => data_element.name = text,
+                    2 => data_element.keyword = text,
                     3 => {
-                        let vr = text.unwrap();
+                        let vr = text;
                         // TODO: not too clean... tags like "Item" have the text "See Note 2" as VR
                         // Note 2 says that these tags do not have a VR
                         if !vr.starts_with("See Note") {
                             data_element.vr = vr;
                         }
                     }
-                    4 => data_element.vm = text.unwrap(),
-                    5 => data_element.comment = text,
+                    4 => data_element.vm = text,
+                    5 => data_element.comment = Some(text),
                     _ => return Err(From::from("Found unexpected number of 'td' elements")),
                 }
-
-                counter += 1;
             }
 
             data_elements.push(data_element);
@@ -295,10 +370,40 @@ impl Parser {
         None
     }
 
-    fn read_content<R: Read>(reader: &mut R) -> Result<String, ::std::io::Error> {
-        let mut content = String::new();
-        reader.read_to_string(&mut content)?;
-        Ok(content)
+    /// Returns the text of each cell of the table row `tr` (including the text
+    /// of e.g. "emphasis" sub-elements) or `None` if a cell is empty.
+    fn row_cells(tr: &xmltree::Element) -> Vec<Option<String>> {
+        tr.children
+            .iter()
+            .filter(|td| td.name == "td")
+            .map(|td| {
+                let text = table::element_text(td);
+                if text.is_empty() {
+                    None
+                } else {
+                    Some(text)
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the text of the cells of all body rows of the table whose
+    /// caption or xml:id is `table_name`, checking that each row has at least
+    /// `min_columns` cells.
+    fn parse_table_rows(
+        &self,
+        table_name: &str,
+        min_columns: usize,
+    ) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+        let rows = self.table(table_name)?.row_texts();
+        if rows.iter().any(|row| row.len() < min_columns) {
+            return Err(From::from(format!(
+                "Found unexpected number of 'td' elements in table '{}'",
+                table_name
+            )));
+        }
+
+        Ok(rows)
     }
 
     fn normalize_uid_name(full_uid_name: &str) -> String {
@@ -320,6 +425,7 @@ impl Parser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fixtures::{fixture, part6_fixture};
 
     #[test]
     fn normalize_uid_name_doesnt_change_input_without_colon_or_retired() {
@@ -337,6 +443,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_well_known_frame_of_reference_registry_reads_table_by_caption() {
+        let frames = Parser::with_part6_file_contents(fixture(6).to_owned())
+            .parse_well_known_frame_of_reference_registry()
+            .unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].value, "1.2.840.10008.1.4.1.1");
+        assert_eq!(frames[0].name, "Talairach Brain Atlas Frame of Reference");
+        assert!(frames[0].normative_reference.starts_with("Talairach J"));
+    }
+
+    #[test]
+    fn parse_context_group_and_template_uid_registries() {
+        let parser = Parser::with_part6_file_contents(fixture(6).to_owned());
+        let context_groups = parser.parse_context_group_uid_registry().unwrap();
+        assert_eq!(context_groups.len(), 2);
+        assert_eq!(context_groups[1].value, "1.2.840.10008.6.1.530");
+        assert_eq!(context_groups[1].cid(), Some(7021));
+
+        let templates = parser.parse_template_uid_registry().unwrap();
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].tid(), Some(1500));
+
+        let older_parser = Parser::with_part6_file_contents(part6_fixture("2019a").to_owned());
+        assert!(older_parser.parse_template_uid_registry().is_err());
+        assert!(older_parser.parse_context_group_uid_registry().is_err());
+    }
+
+    #[test]
+    fn tables_lists_all_tables_with_their_chapter() {
+        let tables = Parser::with_part6_file_contents(fixture(6).to_owned())
+            .tables()
+            .unwrap();
+        assert_eq!(tables.len(), 7);
+        assert_eq!(tables[0].id, "table_6-1");
+        assert_eq!(tables[0].caption, "Registry of DICOM Data Elements");
+        assert_eq!(tables[4].chapter, Some("A".to_owned()));
+        assert_eq!(tables[4].caption, "Well-known Frames of Reference");
+    }
+
+    #[test]
+    fn parse_registries_read_mixed_content_cells() {
+        let parser = Parser::with_part6_file_contents(
+            r#"<book>
+  <chapter label="6">
+    <table>
+      <tbody>
+        <tr>
+          <td><para>(0008,0001)</para></td>
+          <td><para>Length <emphasis>to</emphasis> End</para></td>
+          <td><para>LengthToEnd</para></td>
+          <td><para>UL</para></td>
+          <td><para>1</para></td>
+          <td><para><emphasis role="italic">RET</emphasis></para></td>
+        </tr>
+      </tbody>
+    </table>
+  </chapter>
+  <chapter label="A">
+    <table>
+      <tbody>
+        <tr>
+          <td><para>1.2.840.10008.1.1</para></td>
+          <td><para>Foo <emphasis>Bar</emphasis></para></td>
+          <td><para>SOP Class</para></td>
+          <td><para/></td>
+        </tr>
+      </tbody>
+    </table>
+  </chapter>
+</book>"#
+                .to_owned(),
+        );
+
+        let elements = parser.parse_data_element_registry().unwrap();
+        assert_eq!(elements.len(), 1);
+        assert_eq!(elements[0].name, "Length to End");
+        assert_eq!(elements[0].comment, Some("RET".to_owned()));
+
+        let uids = parser.parse_unique_identifier_registry().unwrap();
+        assert_eq!(uids.len(), 1);
+        assert_eq!(uids[0].full_name, "Foo Bar");
+        assert_eq!(uids[0].kind, Kind::SopClass);
+    }
+
     #[test]
     fn normalize_uid_name_removes_retired() {
         assert_eq!(
@@ -0,0 +1,482 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use dcmtk::DcmtkParser;
+use tag::Tag;
+use vr::VR;
+
+use serde_json;
+
+/// A data element defined by a private data dictionary.
+///
+/// Private data elements are identified by the private creator reserving a
+/// block of elements in an odd group together with the offset of the element
+/// within that block. E.g. the element with offset 0x0A of private creator
+/// "SIEMENS MR HEADER" in group 0x0019 is (0019,100A) if the private creator
+/// is stored in (0019,0010), but (0019,110A) if it is stored in (0019,0011).
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PrivateDataElement {
+    /// The private creator owning the element (e.g. "SIEMENS MR HEADER").
+    pub creator: String,
+
+    /// The (odd) group number of the element (e.g. 0x0019).
+    pub group: u16,
+
+    /// The offset of the element within the block reserved by the private
+    /// creator (e.g. 0x0A for "(0019,xx0A)").
+    pub element_offset: u8,
+
+    /// The name of the element as a human-readable string (e.g. "Number Of
+    /// Images In Mosaic"). Empty if the dictionary does not provide one.
+    pub name: String,
+
+    /// The keyword of the element (e.g. "NumberOfImagesInMosaic").
+    pub keyword: String,
+
+    /// The Value Representation of the element in the same format as
+    /// `DataElement::vr` (e.g. "US" or "OB or OW").
+    pub vr: String,
+
+    /// The Value Multiplicity of the element in the same format as
+    /// `DataElement::vm` (e.g. "1-n").
+    pub vm: String,
+
+    /// Additional comment for the element (e.g. "RET" for retired elements).
+    pub comment: Option<String>,
+}
+
+impl PrivateDataElement {
+    pub fn new() -> PrivateDataElement {
+        Default::default()
+    }
+}
+
+/// A dictionary of private data elements keyed by private creator, group and
+/// element offset.
+///
+/// Dictionaries can be loaded from a simple text format which is compatible
+/// with the private tag entries of DCMTK's "dicom.dic": each line holds the
+/// tab-separated fields tag, VR, keyword, VM and an optional version, where
+/// the tag includes the private creator and the element offset, e.g. (with
+/// tabs between the fields):
+///
+/// ```text
+/// (0019,"SIEMENS MR HEADER",0a) US NumberOfImagesInMosaic 1 PrivateTag
+/// ```
+///
+/// The element offset may also be given as "xx0a" or with the block number
+/// of a typical private creator (e.g. "100a"); only the lower byte is used.
+/// Empty lines, comment lines starting with "#" and entries of public tags are
+/// skipped.
+///
+/// Alternatively, dictionaries can be loaded from JSON holding an array of
+/// objects with the fields "creator", "tag", "vr" and "vm" as well as the
+/// optional fields "keyword", "name" and "retired", where "tag" and "vr" use
+/// the same format as `DataElement`, e.g.:
+///
+/// ```json
+/// [{"creator": "SIEMENS MR HEADER", "tag": "(0019,xx0A)", "vr": "US", "vm": "1",
+///   "keyword": "NumberOfImagesInMosaic", "name": "Number Of Images In Mosaic"}]
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PrivateDictionary {
+    elements: BTreeMap<(String, u16, u8), PrivateDataElement>,
+}
+
+impl PrivateDictionary {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new `PrivateDictionary` from the file given as `file_path`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Reading the file at `file_path` fails
+    /// * The contents of the file cannot be parsed (see
+    ///   `with_file_contents`)
+    pub fn with_file(file_path: &Path) -> Result<Self, Box<dyn Error>> {
+        Self::with_file_contents(&fs::read_to_string(file_path)?)
+    }
+
+    /// Creates a new `PrivateDictionary` given the full `contents` of a
+    /// private dictionary file.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if a line of a private tag does not follow the
+    /// format (e.g. has too few fields, an invalid tag or an unknown VR).
+    pub fn with_file_contents(contents: &str) -> Result<Self, Box<dyn Error>> {
+        let mut dictionary = PrivateDictionary::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || !line.contains('"') {
+                continue;
+            }
+
+            let element = Self::parse_element(line)
+                .map_err(|e| format!("Unable to parse line {} of dictionary: {}", index + 1, e))?;
+            dictionary.insert(element);
+        }
+
+        Ok(dictionary)
+    }
+
+    /// Creates a new `PrivateDictionary` from the JSON file given as
+    /// `file_path`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Reading the file at `file_path` fails
+    /// * The contents of the file cannot be parsed (see
+    ///   `with_json_file_contents`)
+    pub fn with_json_file(file_path: &Path) -> Result<Self, Box<dyn Error>> {
+        Self::with_json_file_contents(&fs::read_to_string(file_path)?)
+    }
+
+    /// Creates a new `PrivateDictionary` given the full `contents` of a
+    /// private dictionary in JSON format.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if `contents` is no valid JSON, is no array of
+    /// objects or an object lacks a required field or has an invalid tag or
+    /// an unknown VR.
+    pub fn with_json_file_contents(contents: &str) -> Result<Self, Box<dyn Error>> {
+        let entries = match serde_json::from_str(contents)? {
+            serde_json::Value::Array(entries) => entries,
+            _ => return Err(From::from("Expected an array of private data elements")),
+        };
+
+        let mut dictionary = PrivateDictionary::new();
+        for (index, entry) in entries.iter().enumerate() {
+            let element = Self::parse_json_element(entry)
+                .map_err(|e| format!("Unable to parse entry {} of dictionary: {}", index, e))?;
+            dictionary.insert(element);
+        }
+
+        Ok(dictionary)
+    }
+
+    /// Inserts `element`, replacing any element with the same private
+    /// creator, group and element offset.
+    pub fn insert(&mut self, element: PrivateDataElement) -> Option<PrivateDataElement> {
+        let key = (
+            Self::normalize_creator(&element.creator),
+            element.group,
+            element.element_offset,
+        );
+        self.elements.insert(key, element)
+    }
+
+    /// Returns the element with the given private `creator`, `group` and
+    /// `element_offset`. Trailing spaces of `creator` (e.g. padding of the
+    /// value of the private creator element) are ignored.
+    pub fn get(
+        &self,
+        creator: &str,
+        group: u16,
+        element_offset: u8,
+    ) -> Option<&PrivateDataElement> {
+        self.elements
+            .get(&(Self::normalize_creator(creator), group, element_offset))
+    }
+
+    /// Returns the element the concrete private `tag` refers to, given the
+    /// private `creators` found in the data set containing the tag.
+    ///
+    /// Returns `None` if `tag` is no private data element (e.g. because it
+    /// is a private creator element itself), if no private creator reserved
+    /// the block of `tag` or if the dictionary does not know the element.
+    pub fn resolve(&self, creators: &PrivateCreators, tag: Tag) -> Option<&PrivateDataElement> {
+        if !tag.is_private() || tag.element < 0x1000 {
+            return None;
+        }
+
+        let creator = creators.creator(tag.group, (tag.element >> 8) as u8)?;
+        self.get(creator, tag.group, (tag.element & 0xFF) as u8)
+    }
+
+    /// Returns all elements ordered by private creator, group and element
+    /// offset.
+    pub fn elements(&self) -> impl Iterator<Item = &PrivateDataElement> {
+        self.elements.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    fn parse_element(line: &str) -> Result<PrivateDataElement, Box<dyn Error>> {
+        // the private creator may contain tabs or commas, so split it off first
+        let first_quote = line.find('"').unwrap();
+        let last_quote = line.rfind('"').unwrap();
+        if first_quote == last_quote {
+            return Err(From::from("Unterminated private creator"));
+        }
+
+        let fields: Vec<&str> = line[last_quote + 1..].split('\t').map(str::trim).collect();
+        if fields.len() < 4 {
+            return Err(From::from(format!(
+                "Expected at least 4 fields, found {}",
+                fields.len()
+            )));
+        }
+
+        let group = line[..first_quote]
+            .trim()
+            .trim_start_matches('(')
+            .trim_end_matches(',')
+            .trim();
+        let element_offset = fields[0]
+            .trim_start_matches(',')
+            .trim_end_matches(')')
+            .trim();
+
+        let mut element = PrivateDataElement::new();
+        element.creator = line[first_quote + 1..last_quote].to_owned();
+        element.group = Self::parse_group(group)?;
+        element.element_offset = Self::parse_element_offset(element_offset)?;
+        element.vr = VR::format_list(&DcmtkParser::parse_vr(fields[1])?);
+        element.vm = fields[3].to_owned();
+
+        let version = fields.get(4).map_or("", |v| *v);
+        if fields[2].starts_with("RETIRED_") || version.to_lowercase().contains("retired") {
+            element.keyword = fields[2].trim_start_matches("RETIRED_").to_owned();
+            element.comment = Some("RET".to_owned());
+        } else {
+            element.keyword = fields[2].to_owned();
+        }
+
+        Ok(element)
+    }
+
+    fn parse_json_element(entry: &serde_json::Value) -> Result<PrivateDataElement, Box<dyn Error>> {
+        let field = |name: &str| -> Result<&str, Box<dyn Error>> {
+            match entry.get(name) {
+                Some(serde_json::Value::String(value)) => Ok(value),
+                Some(_) => Err(From::from(format!("Field '{}' is no string", name))),
+                None => Err(From::from(format!("Missing field '{}'", name))),
+            }
+        };
+        let optional_field = |name: &str| field(name).map(str::to_owned).unwrap_or_default();
+
+        let tag = field("tag")?.trim();
+        let numbers: Vec<&str> = tag
+            .trim_start_matches('(')
+            .trim_end_matches(')')
+            .split(',')
+            .map(str::trim)
+            .collect();
+        if numbers.len() != 2 {
+            return Err(From::from(format!("Invalid tag '{}'", tag)));
+        }
+
+        let mut element = PrivateDataElement::new();
+        element.creator = field("creator")?.to_owned();
+        element.group = Self::parse_group(numbers[0])?;
+        element.element_offset = Self::parse_element_offset(numbers[1])?;
+        element.vr = VR::format_list(&VR::parse_list(field("vr")?)?);
+        element.vm = field("vm")?.to_owned();
+        element.keyword = optional_field("keyword");
+        element.name = optional_field("name");
+        if entry.get("retired").and_then(serde_json::Value::as_bool) == Some(true) {
+            element.comment = Some("RET".to_owned());
+        }
+
+        Ok(element)
+    }
+
+    fn parse_group(group: &str) -> Result<u16, Box<dyn Error>> {
+        let number = u16::from_str_radix(group, 16)?;
+        if number & 1 == 0 {
+            return Err(From::from(format!("Group {} is no private group", group)));
+        }
+
+        Ok(number)
+    }
+
+    /// Parses the lower byte of an element offset like "0a", "xx0a" or "100a".
+    fn parse_element_offset(element_offset: &str) -> Result<u8, Box<dyn Error>> {
+        let is_hex = |digits: &str| digits.chars().all(|c| c.is_ascii_hexdigit());
+        let (block, offset) = match element_offset.len() {
+            2 => ("", element_offset),
+            4 if element_offset.is_ascii() => element_offset.split_at(2),
+            _ => ("", ""),
+        };
+        if offset.is_empty()
+            || !is_hex(offset)
+            || !(is_hex(block) || block.eq_ignore_ascii_case("xx"))
+        {
+            return Err(From::from(format!(
+                "Invalid element offset '{}'",
+                element_offset
+            )));
+        }
+
+        Ok(u8::from_str_radix(offset, 16)?)
+    }
+
+    fn normalize_creator(creator: &str) -> String {
+        creator.trim_end_matches(&[' ', '\0'][..]).to_owned()
+    }
+}
+
+/// The private creators found in a data set, i.e. the values of the private
+/// creator elements (gggg,0010) to (gggg,00FF) of odd groups.
+#[derive(Clone, Debug, Default)]
+pub struct PrivateCreators {
+    creators: HashMap<(u16, u8), String>,
+}
+
+impl PrivateCreators {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Records `creator` as the value of the private creator element `tag`
+    /// (e.g. "SIEMENS MR HEADER" for (0019,0010)).
+    ///
+    /// # Errors
+    ///
+    /// This function fails if `tag` is no private creator element.
+    pub fn insert(&mut self, tag: Tag, creator: &str) -> Result<(), Box<dyn Error>> {
+        if !tag.is_private_creator() {
+            return Err(From::from(format!("{} is no private creator element", tag)));
+        }
+
+        self.creators.insert(
+            (tag.group, tag.element as u8),
+            PrivateDictionary::normalize_creator(creator),
+        );
+        Ok(())
+    }
+
+    /// Returns the private creator reserving `block` (e.g. 0x10 for the
+    /// elements (0019,1000) to (0019,10FF)) of `group`.
+    pub fn creator(&self, group: u16, block: u8) -> Option<&str> {
+        self.creators.get(&(group, block)).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary() -> PrivateDictionary {
+        PrivateDictionary::with_file_contents(
+            "# Siemens\n\
+             (0019,\"SIEMENS MR HEADER\",08)\tCS\tImagingMode\t1\tPrivateTag\n\
+             (0019,\"SIEMENS MR HEADER\",xx0a)\tUS\tNumberOfImagesInMosaic\t1\tPrivateTag\n\
+             (0008,0001)\tUL\tLengthToEnd\t1\tACR/NEMA2\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn with_file_contents_reads_private_entries_only() {
+        let dictionary = dictionary();
+        assert_eq!(dictionary.len(), 2);
+
+        let element = dictionary.get("SIEMENS MR HEADER ", 0x0019, 0x0A).unwrap();
+        assert_eq!(element.keyword, "NumberOfImagesInMosaic");
+        assert_eq!(element.vr, "US");
+        assert_eq!(element.vm, "1");
+    }
+
+    #[test]
+    fn with_json_file_contents_reads_array_of_elements() {
+        let dictionary = PrivateDictionary::with_json_file_contents(
+            r#"[
+                {"creator": "SIEMENS MR HEADER", "tag": "(0019,xx0A)", "vr": "US", "vm": "1",
+                 "keyword": "NumberOfImagesInMosaic", "name": "Number Of Images In Mosaic"},
+                {"creator": "SIEMENS MR HEADER", "tag": "(0019,1008)", "vr": "CS", "vm": "1",
+                 "retired": true}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(dictionary.len(), 2);
+
+        let element = dictionary.get("SIEMENS MR HEADER", 0x0019, 0x0A).unwrap();
+        assert_eq!(element.keyword, "NumberOfImagesInMosaic");
+        assert_eq!(element.name, "Number Of Images In Mosaic");
+        assert_eq!(element.vr, "US");
+        assert!(element.comment.is_none());
+
+        let imaging_mode = dictionary.get("SIEMENS MR HEADER", 0x0019, 0x08).unwrap();
+        assert!(imaging_mode.keyword.is_empty());
+        assert_eq!(imaging_mode.comment, Some("RET".to_owned()));
+
+        let error = PrivateDictionary::with_json_file_contents(
+            r#"[{"creator": "ACME", "tag": "(0018,xx10)", "vr": "LO", "vm": "1"}]"#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("no private group"));
+        assert!(PrivateDictionary::with_json_file_contents(r#"{"creator": "ACME"}"#).is_err());
+    }
+
+    #[test]
+    fn invalid_element_offsets_are_rejected() {
+        for tag in &[
+            "(0019,éa)",
+            "(0019,xxéa)",
+            "(0019,0g)",
+            "(0019,10a)",
+            "(0019,yy0a)",
+        ] {
+            let contents = format!(
+                r#"[{{"creator": "ACME", "tag": "{}", "vr": "LO", "vm": "1"}}]"#,
+                tag
+            );
+            let error = PrivateDictionary::with_json_file_contents(&contents).unwrap_err();
+            assert!(
+                error.to_string().contains("Invalid element offset"),
+                "{}",
+                tag
+            );
+        }
+
+        let error = PrivateDictionary::with_file_contents(
+            "(0019,\"ACME\",éa)\tLO\tAcmeName\t1\tPrivateTag\n",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("Invalid element offset"));
+    }
+
+    #[test]
+    fn resolve_uses_block_of_private_creator() {
+        let dictionary = dictionary();
+        let mut creators = PrivateCreators::new();
+        creators
+            .insert(Tag::new(0x0019, 0x0011), "SIEMENS MR HEADER")
+            .unwrap();
+
+        let element = dictionary
+            .resolve(&creators, Tag::new(0x0019, 0x110A))
+            .unwrap();
+        assert_eq!(element.keyword, "NumberOfImagesInMosaic");
+        assert!(dictionary
+            .resolve(&creators, Tag::new(0x0019, 0x100A))
+            .is_none());
+        assert!(dictionary
+            .resolve(&creators, Tag::new(0x0019, 0x0011))
+            .is_none());
+    }
+
+    #[test]
+    fn private_creators_rejects_non_creator_elements() {
+        let mut creators = PrivateCreators::new();
+        assert!(creators.insert(Tag::new(0x0018, 0x0010), "ACME").is_err());
+        assert!(creators.insert(Tag::new(0x0019, 0x1010), "ACME").is_err());
+    }
+}
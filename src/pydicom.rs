@@ -0,0 +1,184 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use tag::TagRange;
+use vr::VR;
+use DataElement;
+
+/// A parser for data dictionaries in the format of pydicom's
+/// "_dicom_dict.py".
+///
+/// Each entry of such a file maps a tag to a tuple of VR, VM, name, retired
+/// flag and keyword, e.g.:
+///
+/// ```text
+/// 0x00080001: ('UL', '1', "Length to End", 'Retired', 'LengthToEnd'),
+/// '60xx3000': ('OB or OW', '1', "Overlay Data", '', 'OverlayData'),
+/// ```
+pub struct PydicomParser {
+    /// Holds the contents of the dictionary file once read.
+    content: String,
+}
+
+impl PydicomParser {
+    /// Creates a new `PydicomParser` instance using the dictionary file given
+    /// as `file_path`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Opening the file at `file_path` fails
+    /// * Reading the file at `file_path` fails
+    pub fn with_dict_file(file_path: &Path) -> Result<Self, ::std::io::Error> {
+        Ok(Self {
+            content: fs::read_to_string(file_path)?,
+        })
+    }
+
+    /// Creates a new `PydicomParser` instance given the full `contents` of a
+    /// dictionary file.
+    pub fn with_dict_file_contents(contents: String) -> Self {
+        Self { content: contents }
+    }
+
+    /// Returns all data elements defined in the dictionary, including the
+    /// repeating groups of "RepeatersDictionary" (e.g. "60xx3000" becomes
+    /// "(60xx,3000)"). Lines that are not dictionary entries (e.g. comments or
+    /// the dictionary declarations themselves) are skipped, as are entries
+    /// that do not consist of exactly five values (e.g. the ones of private
+    /// dictionaries).
+    ///
+    /// Retired elements have the comment "RET" and the pseudo VR "NONE" of
+    /// e.g. "Item" is returned as empty VR.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if an entry has an invalid tag or an unknown VR.
+    pub fn parse_data_elements(&self) -> Result<Vec<DataElement>, Box<dyn Error>> {
+        let mut data_elements = Vec::new();
+        for (index, line) in self.content.lines().enumerate() {
+            let (key, values) = match Self::split_entry(line) {
+                Some(entry) => entry,
+                None => continue,
+            };
+
+            if values.len() != 5 {
+                continue;
+            }
+
+            let data_element = Self::parse_data_element(key, &values)
+                .map_err(|e| format!("Unable to parse line {} of dictionary: {}", index + 1, e))?;
+            data_elements.push(data_element);
+        }
+
+        Ok(data_elements)
+    }
+
+    fn parse_data_element(key: &str, values: &[String]) -> Result<DataElement, Box<dyn Error>> {
+        let mut data_element = DataElement::new();
+        data_element.tag = Self::parse_tag(key)?.to_string();
+        if values[0] != "NONE" {
+            data_element.vr = VR::format_list(&VR::parse_list(&values[0])?);
+        }
+        data_element.vm = values[1].clone();
+        data_element.name = values[2].clone();
+        if !values[3].is_empty() {
+            data_element.comment = Some("RET".to_owned());
+        }
+        data_element.keyword = values[4].clone();
+        Ok(data_element)
+    }
+
+    /// Parses a key like "0x00080001" or "'60xx3000'".
+    pub(crate) fn parse_tag(key: &str) -> Result<TagRange, Box<dyn Error>> {
+        let digits = key
+            .trim_start_matches("0x")
+            .trim_matches(|c| c == '\'' || c == '"');
+        if digits.len() != 8 || !digits.is_ascii() {
+            return Err(From::from(format!("Invalid tag '{}'", key)));
+        }
+
+        format!("({},{})", &digits[..4], &digits[4..]).parse()
+    }
+
+    /// Splits a line like "key: ('a', 'b'),  # comment" into its key and the
+    /// string values of the tuple. Returns `None` for lines that are no such
+    /// entry.
+    pub(crate) fn split_entry(line: &str) -> Option<(&str, Vec<String>)> {
+        let line = line.trim();
+        let colon_index = line.find(':')?;
+        let key = line[..colon_index].trim();
+        let tuple = line[colon_index + 1..].trim();
+        if key.is_empty() || key.contains(' ') || !tuple.starts_with('(') {
+            return None;
+        }
+
+        Self::parse_string_tuple(&tuple[1..]).map(|values| (key, values))
+    }
+
+    /// Parses the string literals of a tuple up to its closing parenthesis.
+    fn parse_string_tuple(tuple: &str) -> Option<Vec<String>> {
+        let mut values = Vec::new();
+        let mut chars = tuple.chars();
+        loop {
+            let quote = match chars.by_ref().find(|c| !c.is_whitespace() && *c != ',')? {
+                ')' => return Some(values),
+                c @ '\'' | c @ '"' => c,
+                _ => return None,
+            };
+
+            let mut value = String::new();
+            loop {
+                match chars.next()? {
+                    '\\' => value.push(chars.next()?),
+                    c if c == quote => break,
+                    c => value.push(c),
+                }
+            }
+            values.push(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_data_elements_reads_public_and_repeater_entries() {
+        let parser = PydicomParser::with_dict_file_contents(
+            "DicomDictionary = {\n\
+             \x20   0x00080001: ('UL', '1', \"Length to End\", 'Retired', 'LengthToEnd'),  # noqa\n\
+             \x20   0x00100010: ('PN', '1', \"Patient's Name\", '', 'PatientName'),  # noqa\n\
+             \x20   0xFFFEE000: ('NONE', '1', \"Item\", '', 'Item'),  # noqa\n\
+             }\n\
+             RepeatersDictionary = {\n\
+             \x20   '60xx3000': ('OB or OW', '1', \"Overlay Data\", '', 'OverlayData'),  # noqa\n\
+             }\n"
+            .to_owned(),
+        );
+
+        let elements = parser.parse_data_elements().unwrap();
+        assert_eq!(elements.len(), 4);
+
+        assert_eq!(elements[0].tag, "(0008,0001)");
+        assert_eq!(elements[0].comment, Some("RET".to_owned()));
+
+        assert_eq!(elements[1].name, "Patient's Name");
+        assert_eq!(elements[1].keyword, "PatientName");
+        assert!(elements[1].comment.is_none());
+
+        assert_eq!(elements[2].vr, "");
+
+        assert_eq!(elements[3].tag, "(60xx,3000)");
+        assert_eq!(elements[3].vr, "OB or OW");
+    }
+
+    #[test]
+    fn split_entry_skips_non_entries() {
+        assert!(PydicomParser::split_entry("DicomDictionary = {").is_none());
+        assert!(PydicomParser::split_entry("# noqa: comment").is_none());
+    }
+}
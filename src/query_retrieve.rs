@@ -0,0 +1,280 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use dictionary::Dictionary;
+use tag::{Tag, TagRange};
+use DataElement;
+
+/// A Query/Retrieve Information Model as defined in Annex C of part 4.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum InformationModel {
+    PatientRoot,
+    StudyRoot,
+    PatientStudyOnly,
+}
+
+impl InformationModel {
+    /// Returns the information model named in the caption of a key table
+    /// (e.g. "Study Level Keys for the Study Root Query/Retrieve Information
+    /// Model").
+    pub fn from_caption(caption: &str) -> Option<Self> {
+        if caption.contains("Patient/Study Only") {
+            Some(InformationModel::PatientStudyOnly)
+        } else if caption.contains("Patient Root") {
+            Some(InformationModel::PatientRoot)
+        } else if caption.contains("Study Root") {
+            Some(InformationModel::StudyRoot)
+        } else {
+            None
+        }
+    }
+}
+
+/// A level of the Query/Retrieve hierarchy as used in Query/Retrieve Level
+/// (0008,0052).
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum QueryLevel {
+    Patient,
+    Study,
+    Series,
+    Image,
+}
+
+impl QueryLevel {
+    /// Returns the level named at the start of the caption of a key table
+    /// (e.g. "Composite Object Instance Level Keys for ..." for the IMAGE
+    /// level).
+    pub fn from_caption(caption: &str) -> Option<Self> {
+        let level = caption.split(" Level Keys").next()?;
+        match level.trim() {
+            "Patient" => Some(QueryLevel::Patient),
+            "Study" => Some(QueryLevel::Study),
+            "Series" => Some(QueryLevel::Series),
+            "Composite Object Instance" | "Instance" | "Image" => Some(QueryLevel::Image),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for QueryLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            QueryLevel::Patient => "PATIENT",
+            QueryLevel::Study => "STUDY",
+            QueryLevel::Series => "SERIES",
+            QueryLevel::Image => "IMAGE",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for QueryLevel {
+    type Err = Box<dyn Error>;
+
+    /// Parses a value of Query/Retrieve Level (e.g. "STUDY").
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "PATIENT" => Ok(QueryLevel::Patient),
+            "STUDY" => Ok(QueryLevel::Study),
+            "SERIES" => Ok(QueryLevel::Series),
+            "IMAGE" => Ok(QueryLevel::Image),
+            _ => Err(From::from(format!("Unknown query/retrieve level '{}'", s))),
+        }
+    }
+}
+
+/// The type of a key at a Query/Retrieve level.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum KeyType {
+    /// The key identifies the entities of the level ("U").
+    Unique,
+
+    /// The key shall be supported by every SCP ("R").
+    Required,
+
+    /// The key may be supported by an SCP ("O").
+    Optional,
+}
+
+impl FromStr for KeyType {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "U" => Ok(KeyType::Unique),
+            "R" => Ok(KeyType::Required),
+            "O" => Ok(KeyType::Optional),
+            _ => Err(From::from(format!("Unknown key type '{}'", s))),
+        }
+    }
+}
+
+/// A type of attribute matching as defined in section C.2.2.2 of part 4.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MatchingType {
+    SingleValue,
+    ListOfUid,
+    Universal,
+    WildCard,
+    Range,
+    Sequence,
+}
+
+impl MatchingType {
+    /// Returns all matching types mentioned in a remark of a key table (e.g.
+    /// "Shall be retrieved with Single Value, Range, or Universal Matching.")
+    /// in the order of this enum.
+    pub fn from_remark(remark: &str) -> Vec<Self> {
+        if !remark.contains("Matching") {
+            return Vec::new();
+        }
+
+        [
+            ("Single Value", MatchingType::SingleValue),
+            ("List of UID", MatchingType::ListOfUid),
+            ("Universal", MatchingType::Universal),
+            ("Wild Card", MatchingType::WildCard),
+            ("Range", MatchingType::Range),
+            ("Sequence", MatchingType::Sequence),
+        ]
+        .iter()
+        .filter(|&&(name, _)| remark.contains(name))
+        .map(|&(_, matching_type)| matching_type)
+        .collect()
+    }
+}
+
+/// A key of a Query/Retrieve level.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueryKey {
+    /// The name of the attribute (e.g. "Patient's Name").
+    pub name: String,
+
+    /// The tag of the attribute as given in the table (e.g. "(0010,0010)").
+    pub tag: String,
+
+    /// The type of the key.
+    pub key_type: KeyType,
+
+    /// The types of matching that shall be supported for the key, empty if
+    /// the table does not name any.
+    pub matching_types: Vec<MatchingType>,
+
+    /// The remark of the table on the key.
+    pub remark: String,
+}
+
+impl QueryKey {
+    /// Returns the typed form of `tag`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if `tag` is not in the format "(gggg,eeee)".
+    pub fn tag_range(&self) -> Result<TagRange, Box<dyn Error>> {
+        self.tag.parse()
+    }
+
+    /// Returns the data element of the key in `dictionary`.
+    pub fn data_element<'a>(&self, dictionary: &'a Dictionary) -> Option<&'a DataElement> {
+        let range = self.tag_range().ok()?;
+        if range.is_range() {
+            dictionary.get(&range)
+        } else {
+            dictionary.lookup(Tag::new(range.group, range.element))
+        }
+    }
+}
+
+/// All keys of the Query/Retrieve Information Models of part 4 keyed by
+/// information model and level.
+#[derive(Clone, Debug, Default)]
+pub struct QueryRetrieveRegistry {
+    keys: BTreeMap<(InformationModel, QueryLevel), Vec<QueryKey>>,
+}
+
+impl QueryRetrieveRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds `keys` to the keys of `level` of the information model `model`.
+    pub fn insert<I>(&mut self, model: InformationModel, level: QueryLevel, keys: I)
+    where
+        I: IntoIterator<Item = QueryKey>,
+    {
+        self.keys.entry((model, level)).or_default().extend(keys);
+    }
+
+    /// Returns the keys of `level` of the information model `model` in the
+    /// order of the standard.
+    pub fn keys(&self, model: InformationModel, level: QueryLevel) -> &[QueryKey] {
+        self.keys
+            .get(&(model, level))
+            .map(|keys| keys.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Returns the key with the given `tag` at `level` of the information
+    /// model `model`.
+    pub fn key(&self, model: InformationModel, level: QueryLevel, tag: Tag) -> Option<&QueryKey> {
+        self.keys(model, level)
+            .iter()
+            .find(|key| key.tag_range().is_ok_and(|range| range.contains(tag)))
+    }
+
+    /// Returns the unique key of `level` of the information model `model`
+    /// (e.g. Study Instance UID for the study level).
+    pub fn unique_key(&self, model: InformationModel, level: QueryLevel) -> Option<&QueryKey> {
+        self.keys(model, level)
+            .iter()
+            .find(|key| key.key_type == KeyType::Unique)
+    }
+
+    /// Returns all information models and levels with their keys.
+    pub fn levels(&self) -> impl Iterator<Item = (InformationModel, QueryLevel, &[QueryKey])> {
+        self.keys
+            .iter()
+            .map(|(&(model, level), keys)| (model, level, keys.as_slice()))
+    }
+
+    /// Returns the number of information model and level combinations.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn model_level_and_matching_types_are_read_from_table_text() {
+        let caption =
+            "Composite Object Instance Level Keys for the Patient Root Query/Retrieve Information Model";
+        assert_eq!(
+            InformationModel::from_caption(caption),
+            Some(InformationModel::PatientRoot)
+        );
+        assert_eq!(QueryLevel::from_caption(caption), Some(QueryLevel::Image));
+        assert_eq!(QueryLevel::Image.to_string(), "IMAGE");
+        assert_eq!("SERIES".parse::<QueryLevel>().unwrap(), QueryLevel::Series);
+
+        assert_eq!(
+            MatchingType::from_remark(
+                "Shall be retrieved with Single Value, Range, or Universal Matching."
+            ),
+            vec![
+                MatchingType::SingleValue,
+                MatchingType::Universal,
+                MatchingType::Range
+            ]
+        );
+        assert!(MatchingType::from_remark("").is_empty());
+    }
+}
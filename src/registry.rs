@@ -0,0 +1,182 @@
+use std::error::Error;
+
+use keyword::{self, Case};
+use tag::{Tag, TagClass};
+use DataElement;
+use Dictionary;
+use DimseParser;
+use Parser;
+use UID;
+
+/// All registries of part 6 of the DICOM standard parsed at once, e.g. to
+/// compare different editions of the standard. The command elements of part
+/// 7 can be added with `add_command_elements`.
+#[derive(Clone, Debug, Default)]
+pub struct Registry {
+    /// The elements of the "Registry of DICOM Data Elements".
+    pub data_elements: Vec<DataElement>,
+
+    /// The elements of the "Registry of DICOM File Meta Elements".
+    pub file_meta_elements: Vec<DataElement>,
+
+    /// The elements of the "Registry of DICOM Directory Structuring
+    /// Elements".
+    pub directory_structuring_elements: Vec<DataElement>,
+
+    /// The command elements of group 0000 defined in part 7 "Message
+    /// Exchange". Empty unless added with `add_command_elements`.
+    pub command_elements: Vec<DataElement>,
+
+    /// The UIDs of the "Registry of DICOM Unique Identifiers (UIDs)".
+    pub uids: Vec<UID>,
+}
+
+impl Registry {
+    /// Creates a new `Registry` holding all registries parsed by `parser`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if parsing any of the registries fails.
+    pub fn from_parser(parser: &Parser) -> Result<Self, Box<dyn Error>> {
+        Ok(Registry {
+            data_elements: parser.parse_data_element_registry()?,
+            file_meta_elements: parser.parse_file_meta_element_registry()?,
+            directory_structuring_elements: parser
+                .parse_directory_structuring_element_registry()?,
+            command_elements: Vec::new(),
+            uids: parser.parse_unique_identifier_registry()?,
+        })
+    }
+
+    /// Adds the command elements parsed by `parser`, so that they are looked
+    /// up along with the elements of part 6.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if parsing the command element registry fails.
+    pub fn add_command_elements(&mut self, parser: &DimseParser) -> Result<(), Box<dyn Error>> {
+        self.command_elements = parser.parse_command_element_registry()?;
+        Ok(())
+    }
+
+    /// Returns the elements of all element registries.
+    pub fn elements(&self) -> impl Iterator<Item = &DataElement> {
+        self.command_elements
+            .iter()
+            .chain(self.file_meta_elements.iter())
+            .chain(self.directory_structuring_elements.iter())
+            .chain(self.data_elements.iter())
+    }
+
+    /// Returns a `Dictionary` of the elements of all element registries.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the tag of an element is not in the format
+    /// "(gggg,eeee)".
+    pub fn dictionary(&self) -> Result<Dictionary, Box<dyn Error>> {
+        Dictionary::from_elements(self.elements().cloned())
+    }
+
+    /// Returns a `Dictionary` of the elements of all element registries with
+    /// every range (e.g. "(60xx,3000)") replaced by its concrete tags, see
+    /// `Dictionary::flatten`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the tag of an element is not in the format
+    /// "(gggg,eeee)".
+    pub fn flattened_dictionary(&self) -> Result<Dictionary, Box<dyn Error>> {
+        Ok(self.dictionary()?.flatten())
+    }
+
+    /// Returns every identifier generated from the keywords of all element
+    /// registries in the given `case` that is shared by more than one
+    /// element, see `keyword::duplicate_identifiers`.
+    pub fn duplicate_identifiers(&self, case: Case) -> Vec<(String, Vec<&DataElement>)> {
+        keyword::duplicate_identifiers(self.elements(), case)
+    }
+
+    /// Returns the class of `tag`. Items, group lengths and private tags are
+    /// recognized by their tag, all other tags by the registry defining them
+    /// (e.g. command elements only once added with `add_command_elements`).
+    pub fn classify(&self, tag: Tag) -> TagClass {
+        let defines = |elements: &[DataElement]| {
+            elements
+                .iter()
+                .any(|e| e.tag_range().is_ok_and(|range| range.contains(tag)))
+        };
+
+        if tag.is_item() || tag.is_delimitation() {
+            TagClass::ItemOrDelimitation
+        } else if tag.is_group_length() {
+            TagClass::GroupLength
+        } else if tag.is_private_creator() {
+            TagClass::PrivateCreator
+        } else if tag.is_private() {
+            TagClass::Private
+        } else if defines(&self.command_elements) {
+            TagClass::Command
+        } else if defines(&self.file_meta_elements) {
+            TagClass::FileMeta
+        } else if defines(&self.directory_structuring_elements) {
+            TagClass::DirectoryStructuring
+        } else if defines(&self.data_elements) {
+            TagClass::Data
+        } else {
+            TagClass::Unknown
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(tag: &str) -> DataElement {
+        DataElement {
+            tag: tag.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn classify_uses_tag_structure_and_defining_registry() {
+        let registry = Registry {
+            data_elements: vec![element("(0010,0010)"), element("(60xx,3000)")],
+            file_meta_elements: vec![element("(0002,0010)")],
+            directory_structuring_elements: vec![element("(0004,1220)")],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            registry.classify(Tag::new(0xFFFE, 0xE0DD)),
+            TagClass::ItemOrDelimitation
+        );
+        assert_eq!(
+            registry.classify(Tag::new(0x0010, 0x0000)),
+            TagClass::GroupLength
+        );
+        assert_eq!(
+            registry.classify(Tag::new(0x0029, 0x0011)),
+            TagClass::PrivateCreator
+        );
+        assert_eq!(
+            registry.classify(Tag::new(0x0029, 0x1011)),
+            TagClass::Private
+        );
+        assert_eq!(
+            registry.classify(Tag::new(0x0002, 0x0010)),
+            TagClass::FileMeta
+        );
+        assert_eq!(
+            registry.classify(Tag::new(0x0004, 0x1220)),
+            TagClass::DirectoryStructuring
+        );
+        assert_eq!(registry.classify(Tag::new(0x6002, 0x3000)), TagClass::Data);
+        assert_eq!(
+            registry.classify(Tag::new(0x0000, 0x0900)),
+            TagClass::Unknown
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use std::error::Error;
+use std::path::Path;
+
+use deidentification::{ConfidentialityAttribute, DeidentificationRegistry, ProfileOption};
+use document::StandardDocument;
+
+/// A parser for the profiles defined in part 15 "Security and System
+/// Management Profiles" of the DICOM standard.
+pub struct SecurityProfileParser {
+    /// The DICOM standard part 15 xml file once read.
+    document: StandardDocument,
+}
+
+impl SecurityProfileParser {
+    /// Creates a new `SecurityProfileParser` instance with a downloaded
+    /// version of the current part 15 of the DICOM standard.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Downloading part15.xml fails
+    /// * Reading the downloaded part15.xml fails
+    pub fn new() -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            document: StandardDocument::new(15)?,
+        })
+    }
+
+    /// Creates a new `SecurityProfileParser` instance using the part15.xml
+    /// given as `file_path`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Opening the file at `file_path` fails
+    /// * Reading the file at `file_path` fails
+    pub fn with_part15_file(file_path: &Path) -> Result<Self, ::std::io::Error> {
+        Ok(Self {
+            document: StandardDocument::with_file(15, file_path)?,
+        })
+    }
+
+    /// Creates a new `SecurityProfileParser` instance given the full
+    /// `contents` of a part15.xml file.
+    pub fn with_part15_file_contents(contents: String) -> Self {
+        Self {
+            document: StandardDocument::with_file_contents(15, contents),
+        }
+    }
+
+    /// Returns the underlying part 15 document.
+    pub fn document(&self) -> &StandardDocument {
+        &self.document
+    }
+
+    /// Returns the actions of the Attribute Confidentiality Profiles defined
+    /// in the "Application Level Confidentiality Profile Attributes" table of
+    /// Annex E, i.e. the action of the basic profile and of each option for
+    /// every attribute.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Parsing of the part15.xml fails
+    ///   * The "Application Level Confidentiality Profile Attributes" table
+    ///     cannot be found
+    ///   * The table has no "Attribute Name" or "Tag" column
+    pub fn parse_deidentification_registry(
+        &self,
+    ) -> Result<DeidentificationRegistry, Box<dyn Error>> {
+        let caption = "Application Level Confidentiality Profile Attributes";
+        let id = match self
+            .document
+            .tables()?
+            .into_iter()
+            .find(|t| t.caption == caption)
+        {
+            Some(info) => info.id,
+            None => return Err(From::from(format!("Unable to find table '{}'.", caption))),
+        };
+
+        let table = self.document.table(&id)?;
+        let (name_column, tag_column) = match (table.column("Attribute Name"), table.column("Tag"))
+        {
+            (Some(name), Some(tag)) => (name, tag),
+            _ => {
+                return Err(From::from(format!(
+                    "Unable to find columns 'Attribute Name' and 'Tag' in table '{}'.",
+                    caption
+                )))
+            }
+        };
+        let retired_column = table.header.iter().position(|h| h.starts_with("Retd."));
+        let iod_column = table
+            .header
+            .iter()
+            .position(|h| h.starts_with("In Std. Comp. IOD"));
+        let basic_column = table.column("Basic Prof.");
+        let option_columns: Vec<(usize, ProfileOption)> = table
+            .header
+            .iter()
+            .enumerate()
+            .filter_map(|(index, h)| ProfileOption::from_header(h).map(|o| (index, o)))
+            .collect();
+
+        let mut attributes = Vec::new();
+        for row in &table.rows {
+            let text = |column: Option<usize>| {
+                column
+                    .and_then(|c| row.get(c))
+                    .map(|cell| cell.text.trim())
+                    .unwrap_or_default()
+            };
+
+            attributes.push(ConfidentialityAttribute {
+                name: text(Some(name_column)).to_owned(),
+                tag: text(Some(tag_column)).to_owned(),
+                retired: text(retired_column) == "Y",
+                in_standard_iod: text(iod_column) == "Y",
+                basic_profile: text(basic_column).parse().ok(),
+                options: option_columns
+                    .iter()
+                    .filter_map(|&(column, option)| {
+                        text(Some(column))
+                            .parse()
+                            .ok()
+                            .map(|action| (option, action))
+                    })
+                    .collect(),
+            });
+        }
+
+        Ok(DeidentificationRegistry::from_attributes(attributes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deidentification::{Action, ProfileMismatch};
+    use fixtures::fixture;
+    use {DataElement, Dictionary, Tag};
+
+    #[test]
+    fn parse_deidentification_registry_reads_basic_profile_and_options() {
+        let registry = SecurityProfileParser::with_part15_file_contents(fixture(15).to_owned())
+            .parse_deidentification_registry()
+            .unwrap();
+        assert_eq!(registry.len(), 4);
+
+        let study_date = registry.lookup(Tag::new(0x0008, 0x0020)).unwrap();
+        assert_eq!(study_date.name, "Study Date");
+        assert!(study_date.in_standard_iod);
+        assert_eq!(study_date.basic_profile, Some(Action::Zero));
+        assert_eq!(
+            study_date.options[&ProfileOption::RetainLongitudinalModifiedDates],
+            Action::Clean
+        );
+        assert_eq!(study_date.options.len(), 2);
+
+        let sop_instance_uid = registry.lookup(Tag::new(0x0008, 0x0018)).unwrap();
+        assert_eq!(
+            registry.action(Tag::new(0x0008, 0x0018), &[ProfileOption::RetainUids]),
+            Some(Action::Keep)
+        );
+        assert_eq!(sop_instance_uid.basic_profile, Some(Action::ReplaceUid));
+
+        let curve_data = registry.lookup(Tag::new(0x5002, 0x3000)).unwrap();
+        assert_eq!(curve_data.tag, "(50xx,xxxx)");
+        assert!(curve_data.retired);
+        assert_eq!(curve_data.basic_profile, Some(Action::Remove));
+        assert!(registry.lookup(Tag::new(0x0010, 0x0020)).is_none());
+    }
+
+    #[test]
+    fn cross_check_reports_differences_to_data_dictionary() {
+        let registry = SecurityProfileParser::with_part15_file_contents(fixture(15).to_owned())
+            .parse_deidentification_registry()
+            .unwrap();
+        let element = |tag: &str, name: &str| DataElement {
+            tag: tag.to_owned(),
+            name: name.to_owned(),
+            ..Default::default()
+        };
+        let dictionary = Dictionary::from_elements(vec![
+            element("(0008,0018)", "SOP Instance UID"),
+            element("(0008,0020)", "Study Date"),
+            element("(0010,0010)", "Patient Name"),
+            element("(50xx,xxxx)", "Curve Data"),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            registry.cross_check(&dictionary),
+            vec![
+                ProfileMismatch::NameDiffers(
+                    "(0010,0010)".to_owned(),
+                    "Patient's Name".to_owned(),
+                    "Patient Name".to_owned()
+                ),
+                ProfileMismatch::RetiredDiffers("(50xx,xxxx)".to_owned(), true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use std::error::Error;
+use std::path::Path;
+
+use document::StandardDocument;
+use query_retrieve::{InformationModel, MatchingType, QueryKey, QueryLevel, QueryRetrieveRegistry};
+use storage::{StorageCategory, StorageSopClass, StorageSopClassRegistry};
+use table::{self, Table};
+
+/// A parser for the service classes defined in part 4 "Service Class
+/// Specifications" of the DICOM standard.
+pub struct ServiceClassParser {
+    /// The DICOM standard part 4 xml file once read.
+    document: StandardDocument,
+}
+
+impl ServiceClassParser {
+    /// Creates a new `ServiceClassParser` instance with a downloaded version
+    /// of the current part 4 of the DICOM standard.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Downloading part04.xml fails
+    /// * Reading the downloaded part04.xml fails
+    pub fn new() -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            document: StandardDocument::new(4)?,
+        })
+    }
+
+    /// Creates a new `ServiceClassParser` instance using the part04.xml given
+    /// as `file_path`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Opening the file at `file_path` fails
+    /// * Reading the file at `file_path` fails
+    pub fn with_part4_file(file_path: &Path) -> Result<Self, ::std::io::Error> {
+        Ok(Self {
+            document: StandardDocument::with_file(4, file_path)?,
+        })
+    }
+
+    /// Creates a new `ServiceClassParser` instance given the full `contents`
+    /// of a part04.xml file.
+    pub fn with_part4_file_contents(contents: String) -> Self {
+        Self {
+            document: StandardDocument::with_file_contents(4, contents),
+        }
+    }
+
+    /// Returns the underlying part 4 document.
+    pub fn document(&self) -> &StandardDocument {
+        &self.document
+    }
+
+    /// Returns all SOP classes of the Storage Service Class defined in the
+    /// "Standard SOP Classes" table of Annex B.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Parsing of the part04.xml fails
+    ///   * The "Standard SOP Classes" table of Annex B cannot be found
+    ///   * A row of the table has less than three columns
+    pub fn parse_storage_sop_class_registry(
+        &self,
+    ) -> Result<StorageSopClassRegistry, Box<dyn Error>> {
+        // other service classes have tables with the same caption
+        let id = match self
+            .document
+            .tables()?
+            .into_iter()
+            .find(|t| t.caption == "Standard SOP Classes" && t.chapter.as_deref() == Some("B"))
+        {
+            Some(info) => info.id,
+            None => {
+                return Err(From::from(
+                    "Unable to find table 'Standard SOP Classes' of Annex B.",
+                ))
+            }
+        };
+
+        let table = self.document.table(&id)?;
+        let mut sop_classes = Vec::new();
+        for row in &table.rows {
+            if row.len() < 3 {
+                return Err(From::from(
+                    "Found unexpected number of 'td' elements in table 'Standard SOP Classes'",
+                ));
+            }
+
+            let name = row[0].text.clone();
+            sop_classes.push(StorageSopClass {
+                uid: row[1].text.replace("\u{200b}", ""),
+                category: StorageCategory::from_name(&name),
+                name,
+                // the IOD specification is a link into part 3
+                iod_reference: row[2].links.first().cloned(),
+            });
+        }
+
+        Ok(StorageSopClassRegistry::from_sop_classes(sop_classes))
+    }
+
+    /// Returns the keys of all levels of the Query/Retrieve Information
+    /// Models defined in the "... Level Keys for the ... Query/Retrieve
+    /// Information Model" tables of Annex C.
+    ///
+    /// The matching types of each key are read from its remark. Keys nested
+    /// in sequences (prefixed with ">") are skipped.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    ///
+    /// * Parsing of the part04.xml fails
+    /// * The type of a key is unknown
+    pub fn parse_query_retrieve_registry(&self) -> Result<QueryRetrieveRegistry, Box<dyn Error>> {
+        let root = self.document.root()?;
+
+        let mut registry = QueryRetrieveRegistry::new();
+        for (info, element) in table::list_tables(root) {
+            let (model, level) = match (
+                InformationModel::from_caption(&info.caption),
+                QueryLevel::from_caption(&info.caption),
+            ) {
+                (Some(model), Some(level)) if info.caption.contains("Level Keys") => (model, level),
+                _ => continue,
+            };
+
+            let table = Table::from_element(element, info);
+            let (name_column, tag_column, type_column) = match (
+                table.column("Description"),
+                table.column("Tag"),
+                table.column("Type"),
+            ) {
+                (Some(name), Some(tag), Some(key_type)) => (name, tag, key_type),
+                _ => continue,
+            };
+            let remark_column = table.column("Remark/Matching Type");
+
+            let mut keys = Vec::new();
+            for row in &table.rows {
+                let text = |column: Option<usize>| {
+                    column
+                        .and_then(|c| row.get(c))
+                        .map(|cell| cell.text.trim().to_owned())
+                        .unwrap_or_default()
+                };
+
+                let name = text(Some(name_column));
+                if name.starts_with('>') {
+                    continue;
+                }
+
+                let remark = text(remark_column);
+                keys.push(QueryKey {
+                    name,
+                    tag: text(Some(tag_column)),
+                    key_type: text(Some(type_column)).parse()?,
+                    matching_types: MatchingType::from_remark(&remark),
+                    remark,
+                });
+            }
+
+            registry.insert(model, level, keys);
+        }
+
+        Ok(registry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fixtures::{fixture, part6_fixture};
+    use query_retrieve::KeyType;
+    use storage::SopClassMismatch;
+    use {DataElement, Dictionary, Kind, Parser, Tag};
+
+    #[test]
+    fn parse_storage_sop_class_registry_links_iod_specification() {
+        let registry = ServiceClassParser::with_part4_file_contents(fixture(4).to_owned())
+            .parse_storage_sop_class_registry()
+            .unwrap();
+        assert_eq!(registry.len(), 7);
+
+        let ct = registry.get("1.2.840.10008.5.1.4.1.1.2").unwrap();
+        assert_eq!(ct.name, "CT Image Storage");
+        assert_eq!(ct.iod_reference, Some("sect_A.3".to_owned()));
+        assert_eq!(ct.iod_section(), Some("A.3"));
+        assert_eq!(ct.category, StorageCategory::Image);
+
+        assert!(registry.is_storage_sop_class("1.2.840.10008.5.1.4.1.1.88.33"));
+        assert!(!registry.is_storage_sop_class("1.2.840.10008.1.1"));
+    }
+
+    #[test]
+    fn cross_check_reports_unknown_and_renamed_sop_classes() {
+        let registry = ServiceClassParser::with_part4_file_contents(fixture(4).to_owned())
+            .parse_storage_sop_class_registry()
+            .unwrap();
+        let uids = Parser::with_part6_file_contents(part6_fixture("storage").to_owned())
+            .parse_unique_identifier_registry()
+            .unwrap();
+
+        assert_eq!(
+            registry.cross_check(&uids),
+            vec![
+                SopClassMismatch::WrongKind(
+                    "1.2.840.10008.5.1.4.1.1.104.1".to_owned(),
+                    Kind::MetaSopClass
+                ),
+                SopClassMismatch::NameDiffers(
+                    "1.2.840.10008.5.1.4.1.1.11.1".to_owned(),
+                    "Grayscale Softcopy Presentation State Storage".to_owned(),
+                    "Grayscale Softcopy Presentation State Storage SOP Class".to_owned()
+                ),
+                SopClassMismatch::Unknown("1.2.840.10008.5.1.4.1.1.66".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_query_retrieve_registry_reads_keys_per_model_and_level() {
+        let registry = ServiceClassParser::with_part4_file_contents(fixture(4).to_owned())
+            .parse_query_retrieve_registry()
+            .unwrap();
+        assert_eq!(registry.len(), 4);
+
+        let patient = registry.keys(InformationModel::PatientRoot, QueryLevel::Patient);
+        assert_eq!(patient.len(), 4);
+        assert_eq!(patient[0].key_type, KeyType::Required);
+        assert_eq!(
+            patient[0].matching_types,
+            vec![
+                MatchingType::SingleValue,
+                MatchingType::Universal,
+                MatchingType::WildCard
+            ]
+        );
+        assert!(patient[2].matching_types.is_empty());
+
+        let study_instance_uid = registry
+            .unique_key(InformationModel::StudyRoot, QueryLevel::Study)
+            .unwrap();
+        assert_eq!(study_instance_uid.tag, "(0020,000D)");
+        assert_eq!(
+            study_instance_uid.matching_types,
+            vec![MatchingType::SingleValue, MatchingType::ListOfUid]
+        );
+        assert_eq!(
+            registry
+                .unique_key(InformationModel::PatientRoot, QueryLevel::Image)
+                .unwrap()
+                .name,
+            "SOP Instance UID"
+        );
+        assert!(registry
+            .keys(InformationModel::StudyRoot, QueryLevel::Series)
+            .is_empty());
+
+        let dictionary = Dictionary::from_elements(vec![DataElement {
+            tag: "(0008,0020)".to_owned(),
+            name: "Study Date".to_owned(),
+            vr: "DA".to_owned(),
+            ..Default::default()
+        }])
+        .unwrap();
+        let study_date = registry
+            .key(
+                InformationModel::PatientRoot,
+                QueryLevel::Study,
+                Tag::new(0x0008, 0x0020),
+            )
+            .unwrap();
+        assert!(study_date.matching_types.contains(&MatchingType::Range));
+        assert_eq!(study_date.data_element(&dictionary).unwrap().vr, "DA");
+    }
+}
@@ -0,0 +1,317 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use document::StandardDocument;
+use table::{self, Table};
+
+/// The category of a DIMSE status as defined in Annex C "Status Type
+/// Encoding" of part 7.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum StatusCategory {
+    Success,
+    Pending,
+    Cancel,
+    Warning,
+    Failure,
+}
+
+impl StatusCategory {
+    /// Returns the category of `status` as derived from its value alone, e.g.
+    /// for statuses not listed in any table of the standard.
+    pub fn of(status: u16) -> Self {
+        match status {
+            0x0000 => StatusCategory::Success,
+            0xFF00 | 0xFF01 => StatusCategory::Pending,
+            0xFE00 => StatusCategory::Cancel,
+            0x0001 | 0x0107 | 0x0116 | 0xB000..=0xBFFF => StatusCategory::Warning,
+            _ => StatusCategory::Failure,
+        }
+    }
+}
+
+impl fmt::Display for StatusCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl FromStr for StatusCategory {
+    type Err = Box<dyn Error>;
+
+    /// Parses the "Service Status" column of a status table (e.g. "Success"
+    /// or "Failure"). "Refused", "Error" and "Failed" count as failures.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "Success" => Ok(StatusCategory::Success),
+            "Pending" => Ok(StatusCategory::Pending),
+            "Cancel" | "Canceled" | "Cancelled" => Ok(StatusCategory::Cancel),
+            "Warning" => Ok(StatusCategory::Warning),
+            "Failure" | "Failed" | "Refused" | "Error" => Ok(StatusCategory::Failure),
+            _ => Err(From::from(format!("Unknown status category '{}'", s))),
+        }
+    }
+}
+
+/// A single status code or range of status codes (e.g. "A7xx") together
+/// with its meaning.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct StatusCode {
+    /// The first status code covered.
+    pub first: u16,
+
+    /// The last status code covered. Equal to `first` for single codes.
+    pub last: u16,
+
+    /// The meaning of the status (e.g. "Refused: Out of Resources").
+    pub meaning: String,
+
+    /// The category of the status.
+    pub category: StatusCategory,
+
+    /// The DIMSE service the status is specific to (e.g. "C-STORE"), or
+    /// `None` for general statuses.
+    pub service: Option<String>,
+}
+
+impl StatusCode {
+    /// Returns whether this stands for more than one status code.
+    pub fn is_range(&self) -> bool {
+        self.first != self.last
+    }
+
+    /// Returns whether `status` is covered by this code or range.
+    pub fn contains(&self, status: u16) -> bool {
+        self.first <= status && status <= self.last
+    }
+
+    /// Parses the codes of a cell of a status table, which lists single codes
+    /// (e.g. "0000"), codes with wildcard digits (e.g. "A7xx") or ranges (e.g.
+    /// "A700-A7FF"), separated by commas or line breaks.
+    fn parse_codes(s: &str) -> Vec<(u16, u16)> {
+        let mut codes = Vec::new();
+        for token in s.split(|c: char| c == ',' || c.is_whitespace()) {
+            let mut bounds = token.splitn(2, '-');
+            let first = bounds.next().and_then(Self::parse_code);
+            match (first, bounds.next()) {
+                (Some((first, _)), Some(last)) => {
+                    if let Some((_, last)) = Self::parse_code(last) {
+                        codes.push((first, last));
+                    }
+                }
+                (Some(code), None) => codes.push(code),
+                _ => {}
+            }
+        }
+
+        codes
+    }
+
+    /// Parses a single code of four hexadecimal digits, each of which may be
+    /// replaced by "x", into the first and last code covered.
+    fn parse_code(s: &str) -> Option<(u16, u16)> {
+        if s.len() != 4 {
+            return None;
+        }
+
+        let mut first = 0;
+        let mut last = 0;
+        for c in s.chars() {
+            first <<= 4;
+            last <<= 4;
+            if c == 'x' || c == 'X' {
+                last |= 0xF;
+                continue;
+            }
+
+            let digit = c.to_digit(16)? as u16;
+            first |= digit;
+            last |= digit;
+        }
+
+        Some((first, last))
+    }
+}
+
+impl fmt::Display for StatusCode {
+    /// Formats the code as "A700", a range covering whole digits as "A7xx"
+    /// and any other range as "A700-A7FE".
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let wildcards = (0..4)
+            .take_while(|digit| {
+                let shift = digit * 4;
+                (self.first >> shift) & 0xF == 0 && (self.last >> shift) & 0xF == 0xF
+            })
+            .count();
+        let prefix_shift = wildcards * 4;
+        if prefix_shift == 16 || (self.first >> prefix_shift) == (self.last >> prefix_shift) {
+            let prefix = format!("{:04X}", self.first);
+            write!(f, "{}{}", &prefix[..4 - wildcards], "x".repeat(wildcards))
+        } else {
+            write!(f, "{:04X}-{:04X}", self.first, self.last)
+        }
+    }
+}
+
+/// All status codes defined in the status tables of the standard.
+#[derive(Clone, Debug, Default)]
+pub struct StatusCodeRegistry {
+    codes: Vec<StatusCode>,
+}
+
+impl StatusCodeRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new `StatusCodeRegistry` containing `codes`.
+    pub fn from_codes(codes: Vec<StatusCode>) -> Self {
+        StatusCodeRegistry { codes }
+    }
+
+    /// Returns the definition of `status` for the given DIMSE `service` (e.g.
+    /// "C-STORE"). Statuses specific to the service take precedence over
+    /// general ones, single codes take precedence over ranges.
+    pub fn lookup(&self, status: u16, service: Option<&str>) -> Option<&StatusCode> {
+        self.codes
+            .iter()
+            .filter(|c| c.contains(status))
+            .filter(|c| c.service.is_none() || c.service.as_deref() == service)
+            .min_by_key(|c| (c.service.is_none(), c.last - c.first))
+    }
+
+    /// Returns the definitions of `status` for all services.
+    pub fn lookup_all(&self, status: u16) -> Vec<&StatusCode> {
+        self.codes.iter().filter(|c| c.contains(status)).collect()
+    }
+
+    /// Returns all status codes in the order of their definition.
+    pub fn codes(&self) -> &[StatusCode] {
+        &self.codes
+    }
+
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+}
+
+/// Returns the status codes of all status tables of `document`, i.e. all
+/// tables with a "Service Status" column and a column of codes.
+pub(crate) fn parse_status_codes(
+    document: &StandardDocument,
+) -> Result<Vec<StatusCode>, Box<dyn Error>> {
+    let root = document.root()?;
+    Ok(table::list_tables(root)
+        .into_iter()
+        .flat_map(|(info, element)| status_codes(&Table::from_element(element, info)))
+        .collect())
+}
+
+fn status_codes(table: &Table) -> Vec<StatusCode> {
+    let category_column = table.column("Service Status");
+    let code_column = ["Status Codes", "Status Code", "Error Codes", "Code"]
+        .iter()
+        .filter_map(|name| table.column(name))
+        .next();
+    let (category_column, code_column) = match (category_column, code_column) {
+        (Some(category), Some(code)) => (category, code),
+        _ => return Vec::new(),
+    };
+    let meaning_column = table
+        .column("Further Meaning")
+        .or_else(|| table.column("Meaning"));
+    let service = table
+        .info
+        .caption
+        .split_whitespace()
+        .find(|word| is_dimse_service(word))
+        .map(|word| word.to_owned());
+
+    let mut codes = Vec::new();
+    for row in &table.rows {
+        let (category, code) = match (row.get(category_column), row.get(code_column)) {
+            (Some(category), Some(code)) => (category, code),
+            _ => continue,
+        };
+        let meaning = meaning_column
+            .and_then(|c| row.get(c))
+            .map(|cell| cell.text.clone())
+            .unwrap_or_default();
+        for (first, last) in StatusCode::parse_codes(&code.text) {
+            codes.push(StatusCode {
+                first,
+                last,
+                meaning: if meaning.is_empty() {
+                    category.text.clone()
+                } else {
+                    meaning.clone()
+                },
+                category: category
+                    .text
+                    .parse()
+                    .unwrap_or_else(|_| StatusCategory::of(first)),
+                service: service.clone(),
+            });
+        }
+    }
+
+    codes
+}
+
+/// Returns whether `word` is the name of a DIMSE service (e.g. "C-STORE" or
+/// "N-EVENT-REPORT").
+fn is_dimse_service(word: &str) -> bool {
+    (word.starts_with("C-") || word.starts_with("N-"))
+        && word.len() > 2
+        && word[2..]
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(first: u16, last: u16, service: Option<&str>) -> StatusCode {
+        StatusCode {
+            first,
+            last,
+            meaning: String::new(),
+            category: StatusCategory::of(first),
+            service: service.map(|s| s.to_owned()),
+        }
+    }
+
+    #[test]
+    fn status_codes_parse_and_format_wildcards_and_ranges() {
+        assert_eq!(
+            StatusCode::parse_codes("A7xx, 0000\nA900-A9FE"),
+            vec![(0xA700, 0xA7FF), (0x0000, 0x0000), (0xA900, 0xA9FE)]
+        );
+        assert_eq!(code(0xA700, 0xA7FF, None).to_string(), "A7xx");
+        assert_eq!(code(0xC000, 0xCFFF, None).to_string(), "Cxxx");
+        assert_eq!(code(0xA900, 0xA9FE, None).to_string(), "A900-A9FE");
+        assert_eq!(code(0x0107, 0x0107, None).to_string(), "0107");
+    }
+
+    #[test]
+    fn lookup_prefers_service_specific_and_single_codes() {
+        let registry = StatusCodeRegistry::from_codes(vec![
+            code(0xA700, 0xA7FF, Some("C-STORE")),
+            code(0xA700, 0xA700, None),
+            code(0xC000, 0xCFFF, Some("C-STORE")),
+        ]);
+        assert_eq!(
+            registry.lookup(0xA700, Some("C-STORE")).unwrap().last,
+            0xA7FF
+        );
+        assert_eq!(registry.lookup(0xA700, None).unwrap().last, 0xA700);
+        assert!(registry.lookup(0xC001, Some("C-FIND")).is_none());
+        assert_eq!(registry.lookup_all(0xA700).len(), 2);
+        assert_eq!(StatusCategory::of(0xB007), StatusCategory::Warning);
+    }
+}
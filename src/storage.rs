@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use Kind;
+use UID;
+
+/// The category of objects stored by a storage SOP class, derived from the
+/// name of the SOP class.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum StorageCategory {
+    Image,
+    Waveform,
+    StructuredReport,
+    PresentationState,
+    EncapsulatedDocument,
+    RawData,
+    Other,
+}
+
+impl StorageCategory {
+    /// Returns the category of the storage SOP class with the given `name`
+    /// (e.g. "CT Image Storage" is an image).
+    pub fn from_name(name: &str) -> Self {
+        if name.contains("Presentation State") {
+            StorageCategory::PresentationState
+        } else if name.contains("Image") {
+            StorageCategory::Image
+        } else if name.contains("Waveform") || name.contains("ECG") || name.contains("Audio") {
+            StorageCategory::Waveform
+        } else if name.contains(" SR ") || name.contains("Key Object Selection") {
+            StorageCategory::StructuredReport
+        } else if name.starts_with("Encapsulated") {
+            StorageCategory::EncapsulatedDocument
+        } else if name.starts_with("Raw Data") {
+            StorageCategory::RawData
+        } else {
+            StorageCategory::Other
+        }
+    }
+}
+
+/// A SOP class of the Storage Service Class as defined in the "Standard SOP
+/// Classes" table of part 4.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct StorageSopClass {
+    /// The UID of the SOP class (e.g. "1.2.840.10008.5.1.4.1.1.2").
+    pub uid: String,
+
+    /// The name of the SOP class (e.g. "CT Image Storage").
+    pub name: String,
+
+    /// The target of the link to the IOD specification in part 3 (e.g.
+    /// "sect_A.3"), if any.
+    pub iod_reference: Option<String>,
+
+    /// The category of the stored objects.
+    pub category: StorageCategory,
+}
+
+impl StorageSopClass {
+    /// Returns the label of the section of part 3 specifying the IOD (e.g.
+    /// "A.3" for the "CT Image IOD").
+    pub fn iod_section(&self) -> Option<&str> {
+        self.iod_reference
+            .as_ref()
+            .map(|reference| reference.trim_start_matches("sect_"))
+    }
+}
+
+/// A difference between the storage SOP classes of part 4 and the UID
+/// registry of part 6.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SopClassMismatch {
+    /// The UID of the SOP class is not part of the UID registry.
+    Unknown(String),
+
+    /// The UID is registered with another kind than "SOP Class".
+    WrongKind(String, Kind),
+
+    /// The UID is registered with another name (UID, name in part 4, name in
+    /// part 6).
+    NameDiffers(String, String, String),
+}
+
+impl fmt::Display for SopClassMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SopClassMismatch::Unknown(ref uid) => {
+                write!(f, "{} is not part of the UID registry", uid)
+            }
+            SopClassMismatch::WrongKind(ref uid, ref kind) => {
+                write!(f, "{} is registered as {:?}", uid, kind)
+            }
+            SopClassMismatch::NameDiffers(ref uid, ref part4, ref part6) => {
+                write!(
+                    f,
+                    "{} is named '{}' in part 6 instead of '{}'",
+                    uid, part6, part4
+                )
+            }
+        }
+    }
+}
+
+/// All storage SOP classes keyed by their UID.
+#[derive(Clone, Debug, Default)]
+pub struct StorageSopClassRegistry {
+    sop_classes: BTreeMap<String, StorageSopClass>,
+}
+
+impl StorageSopClassRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new `StorageSopClassRegistry` containing `sop_classes`.
+    pub fn from_sop_classes<I>(sop_classes: I) -> Self
+    where
+        I: IntoIterator<Item = StorageSopClass>,
+    {
+        StorageSopClassRegistry {
+            sop_classes: sop_classes
+                .into_iter()
+                .map(|sop_class| (sop_class.uid.clone(), sop_class))
+                .collect(),
+        }
+    }
+
+    /// Returns the storage SOP class with the UID `value`.
+    pub fn get(&self, value: &str) -> Option<&StorageSopClass> {
+        self.sop_classes.get(value)
+    }
+
+    /// Returns whether `value` is the UID of a storage SOP class.
+    pub fn is_storage_sop_class(&self, value: &str) -> bool {
+        self.sop_classes.contains_key(value)
+    }
+
+    /// Returns all storage SOP classes ordered by UID.
+    pub fn sop_classes(&self) -> impl Iterator<Item = &StorageSopClass> {
+        self.sop_classes.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.sop_classes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sop_classes.is_empty()
+    }
+
+    /// Compares all storage SOP classes with the UID registry `uids` of part
+    /// 6 and returns every mismatch. Names are compared without the suffix
+    /// " (Retired)".
+    pub fn cross_check(&self, uids: &[UID]) -> Vec<SopClassMismatch> {
+        let uids: BTreeMap<&str, &UID> = uids.iter().map(|u| (u.value.as_str(), u)).collect();
+
+        let mut mismatches = Vec::new();
+        for sop_class in self.sop_classes.values() {
+            let uid = match uids.get(sop_class.uid.as_str()) {
+                Some(uid) => uid,
+                None => {
+                    mismatches.push(SopClassMismatch::Unknown(sop_class.uid.clone()));
+                    continue;
+                }
+            };
+
+            if uid.kind != Kind::SopClass {
+                mismatches.push(SopClassMismatch::WrongKind(
+                    sop_class.uid.clone(),
+                    uid.kind.clone(),
+                ));
+            }
+
+            if uid.normalized_name != sop_class.name.replace(" (Retired)", "") {
+                mismatches.push(SopClassMismatch::NameDiffers(
+                    sop_class.uid.clone(),
+                    sop_class.name.clone(),
+                    uid.full_name.clone(),
+                ));
+            }
+        }
+
+        mismatches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn category_is_derived_from_name() {
+        assert_eq!(
+            StorageCategory::from_name("Enhanced MR Image Storage"),
+            StorageCategory::Image
+        );
+        assert_eq!(
+            StorageCategory::from_name("Grayscale Softcopy Presentation State Storage"),
+            StorageCategory::PresentationState
+        );
+        assert_eq!(
+            StorageCategory::from_name("Comprehensive SR Storage"),
+            StorageCategory::StructuredReport
+        );
+        assert_eq!(
+            StorageCategory::from_name("General Audio Waveform Storage"),
+            StorageCategory::Waveform
+        );
+    }
+}
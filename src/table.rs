@@ -0,0 +1,377 @@
+use std::fmt;
+
+use document::TEXT_NODE;
+
+use xmltree;
+
+/// Information about a table of a DICOM docbook document.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TableInfo {
+    /// The xml:id of the table (e.g. "table_6-1"). Empty if the table has
+    /// none.
+    pub id: String,
+
+    /// The caption of the table (e.g. "Registry of DICOM Data Elements").
+    pub caption: String,
+
+    /// The label of the chapter containing the table (e.g. "6" or "A").
+    pub chapter: Option<String>,
+
+    /// The label of the innermost section containing the table (e.g.
+    /// "C.7.1.1"), if the table is not a direct child of its chapter.
+    pub section: Option<String>,
+
+    /// The xml:id of the innermost section containing the table (e.g.
+    /// "sect_C.7.1.1"), if any.
+    pub section_id: Option<String>,
+}
+
+/// A single cell of a table.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Cell {
+    /// The text of the cell. The text of multiple paragraphs is separated by
+    /// line breaks, formatting like "emphasis" is dropped.
+    pub text: String,
+
+    /// The targets of all links within the cell (e.g. "sect_C.7.1.1" for an
+    /// "xref" with that "linkend").
+    pub links: Vec<String>,
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// A table of a DICOM docbook document.
+///
+/// Cells spanning multiple rows or columns (via "rowspan"/"colspan") are
+/// repeated in every row and column they span, so that each row has one cell
+/// per column.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Table {
+    /// Information about where the table is located.
+    pub info: TableInfo,
+
+    /// The text of the header of each column. For tables with multiple header
+    /// rows this is the last one.
+    pub header: Vec<String>,
+
+    /// The cells of all body rows.
+    pub rows: Vec<Vec<Cell>>,
+}
+
+impl Table {
+    /// Returns the index of the column whose header is `name`.
+    pub fn column(&self, name: &str) -> Option<usize> {
+        self.header.iter().position(|h| h == name)
+    }
+
+    /// Returns the text of the cells of all body rows.
+    pub fn row_texts(&self) -> Vec<Vec<String>> {
+        self.rows
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.text.clone()).collect())
+            .collect()
+    }
+
+    pub(crate) fn from_element(table: &xmltree::Element, info: TableInfo) -> Self {
+        let header = table
+            .get_child("thead")
+            .map(Self::expand_spans)
+            .and_then(|rows| rows.into_iter().last())
+            .map(|row| row.into_iter().map(|cell| cell.text).collect())
+            .unwrap_or_default();
+
+        let rows = table
+            .children
+            .iter()
+            .filter(|c| c.name == "tbody")
+            .flat_map(Self::expand_spans)
+            .collect();
+
+        Table { info, header, rows }
+    }
+
+    /// Returns the cells of all rows of `group` (a "thead" or "tbody") with
+    /// cells spanning multiple rows or columns repeated accordingly.
+    fn expand_spans(group: &xmltree::Element) -> Vec<Vec<Cell>> {
+        // cells of previous rows still spanning into the next rows by column
+        let mut pending: Vec<Option<(usize, Cell)>> = Vec::new();
+        let mut rows = Vec::new();
+        for tr in group.children.iter().filter(|c| c.name == "tr") {
+            let mut row = Vec::new();
+            let mut cells = tr
+                .children
+                .iter()
+                .filter(|c| c.name == "td" || c.name == "th");
+            loop {
+                let column = row.len();
+                if let Some(Some((remaining, cell))) = pending.get(column).cloned() {
+                    pending[column] = if remaining > 1 {
+                        Some((remaining - 1, cell.clone()))
+                    } else {
+                        None
+                    };
+                    row.push(cell);
+                    continue;
+                }
+
+                let td = match cells.next() {
+                    Some(td) => td,
+                    None => break,
+                };
+
+                let cell = Cell {
+                    text: element_text(td),
+                    links: element_links(td),
+                };
+                let colspan = span(td, "colspan");
+                let rowspan = span(td, "rowspan");
+                for _ in 0..colspan {
+                    if rowspan > 1 {
+                        if pending.len() <= row.len() {
+                            pending.resize(row.len() + 1, None);
+                        }
+                        pending[row.len()] = Some((rowspan - 1, cell.clone()));
+                    }
+                    row.push(cell.clone());
+                }
+            }
+            rows.push(row);
+        }
+
+        rows
+    }
+}
+
+/// Returns information about all tables in `root` in document order.
+pub(crate) fn list_tables(root: &xmltree::Element) -> Vec<(TableInfo, &xmltree::Element)> {
+    let mut tables = Vec::new();
+    collect_tables(root, &TableInfo::default(), &mut tables);
+    tables
+}
+
+/// Returns the table in `root` whose xml:id or caption is `table_name`.
+pub(crate) fn find_table(root: &xmltree::Element, table_name: &str) -> Option<Table> {
+    list_tables(root)
+        .into_iter()
+        .find(|(info, _)| info.id == table_name || info.caption == table_name)
+        .map(|(info, table)| Table::from_element(table, info))
+}
+
+fn collect_tables<'a>(
+    element: &'a xmltree::Element,
+    context: &TableInfo,
+    tables: &mut Vec<(TableInfo, &'a xmltree::Element)>,
+) {
+    for child in &element.children {
+        match child.name.as_ref() {
+            "chapter" => {
+                let context = TableInfo {
+                    chapter: child.attributes.get("label").cloned(),
+                    ..Default::default()
+                };
+                collect_tables(child, &context, tables);
+            }
+            "section" => {
+                let context = TableInfo {
+                    section: child.attributes.get("label").cloned(),
+                    section_id: child.attributes.get("id").cloned(),
+                    ..context.clone()
+                };
+                collect_tables(child, &context, tables);
+            }
+            "table" => {
+                let info = TableInfo {
+                    id: child.attributes.get("id").cloned().unwrap_or_default(),
+                    caption: child
+                        .get_child("caption")
+                        .map(element_text)
+                        .unwrap_or_default(),
+                    ..context.clone()
+                };
+                tables.push((info, child));
+            }
+            _ => collect_tables(child, context, tables),
+        }
+    }
+}
+
+fn span(cell: &xmltree::Element, attribute: &str) -> usize {
+    cell.attributes
+        .get(attribute)
+        .and_then(|span| span.parse().ok())
+        .unwrap_or(1)
+        .max(1)
+}
+
+/// Returns the text of `element` including the text of all of its
+/// descendants (e.g. of "emphasis" sub-elements) with whitespace collapsed.
+/// The text of block elements like paragraphs or list items is separated by
+/// line breaks, superscripts are prefixed with "^" (e.g. "2^32-2").
+pub(crate) fn element_text(element: &xmltree::Element) -> String {
+    if element.children.iter().any(|c| c.name == TEXT_NODE) {
+        return mixed_text(element);
+    }
+
+    let mut parts: Vec<String> = element
+        .children
+        .iter()
+        .map(child_text)
+        .filter(|text| !text.is_empty())
+        .collect();
+    if let Some(ref text) = element.text {
+        let text = collapse_whitespace(text);
+        if !text.is_empty() {
+            parts.push(text);
+        }
+    }
+
+    let has_blocks = element.children.iter().any(is_block);
+    parts.join(if has_blocks { "\n" } else { " " })
+}
+
+/// Returns the text of `element` mixing text runs and child elements in
+/// document order, see `element_text`.
+fn mixed_text(element: &xmltree::Element) -> String {
+    let mut parts = Vec::new();
+    let mut inline = String::new();
+    for child in &element.children {
+        if child.name == TEXT_NODE {
+            inline.push_str(child.text.as_deref().unwrap_or_default());
+        } else if is_block(child) {
+            parts.push(collapse_whitespace(&inline));
+            inline.clear();
+            parts.push(element_text(child));
+        } else {
+            inline.push_str(&child_text(child));
+        }
+    }
+    parts.push(collapse_whitespace(&inline));
+
+    parts.retain(|part| !part.is_empty());
+    parts.join("\n")
+}
+
+fn child_text(child: &xmltree::Element) -> String {
+    let text = element_text(child);
+    if child.name == "superscript" && !text.is_empty() {
+        format!("^{}", text)
+    } else {
+        text
+    }
+}
+
+fn is_block(element: &xmltree::Element) -> bool {
+    [
+        "para",
+        "listitem",
+        "itemizedlist",
+        "orderedlist",
+        "variablelist",
+        "varlistentry",
+        "note",
+    ]
+    .contains(&element.name.as_str())
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Returns the targets of all links in `element` and its descendants.
+pub(crate) fn element_links(element: &xmltree::Element) -> Vec<String> {
+    let mut links = Vec::new();
+    for attribute in &["linkend", "targetptr", "href"] {
+        if let Some(target) = element.attributes.get(*attribute) {
+            links.push(target.clone());
+        }
+    }
+
+    for child in &element.children {
+        links.extend(element_links(child));
+    }
+
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use document::parse_xml;
+
+    const DOCUMENT: &str = r#"<book xmlns="http://docbook.org/ns/docbook">
+  <chapter label="C" xml:id="chapter_C">
+    <section label="C.7.1.1" xml:id="sect_C.7.1.1">
+      <table xml:id="table_C.7-1">
+        <caption>Patient Module Attributes</caption>
+        <thead>
+          <tr><th><para>Attribute Name</para></th><th><para>Tag</para></th><th><para>Type</para></th></tr>
+        </thead>
+        <tbody>
+          <tr>
+            <td><para>Patient's Name</para></td>
+            <td><para>(0010,0010)</para></td>
+            <td rowspan="2"><para><emphasis role="italic">2</emphasis></para></td>
+          </tr>
+          <tr>
+            <td><para>Patient ID</para></td>
+            <td><para>(0010,0020)</para></td>
+          </tr>
+          <tr>
+            <td colspan="2"><para>Include</para><para><xref linkend="table_10-18"/></para></td>
+            <td><para>3</para></td>
+          </tr>
+        </tbody>
+      </table>
+    </section>
+  </chapter>
+</book>"#;
+
+    #[test]
+    fn list_tables_reports_chapter_and_section() {
+        let root = parse_xml(DOCUMENT).unwrap();
+        let tables = list_tables(&root);
+        assert_eq!(tables.len(), 1);
+
+        let info = &tables[0].0;
+        assert_eq!(info.id, "table_C.7-1");
+        assert_eq!(info.caption, "Patient Module Attributes");
+        assert_eq!(info.chapter, Some("C".to_owned()));
+        assert_eq!(info.section, Some("C.7.1.1".to_owned()));
+        assert_eq!(info.section_id, Some("sect_C.7.1.1".to_owned()));
+    }
+
+    #[test]
+    fn find_table_expands_spans() {
+        let root = parse_xml(DOCUMENT).unwrap();
+        let table = find_table(&root, "Patient Module Attributes").unwrap();
+        assert_eq!(table.header, vec!["Attribute Name", "Tag", "Type"]);
+        assert_eq!(table.column("Type"), Some(2));
+        assert_eq!(
+            table.row_texts(),
+            vec![
+                vec!["Patient's Name", "(0010,0010)", "2"],
+                vec!["Patient ID", "(0010,0020)", "2"],
+                vec!["Include", "Include", "3"],
+            ]
+        );
+        assert_eq!(table.rows[2][0].links, vec!["table_10-18"]);
+        assert!(find_table(&root, "table_C.7-1").is_some());
+    }
+
+    #[test]
+    fn element_text_keeps_mixed_content_in_document_order() {
+        let root = parse_xml(
+            r#"<td><para>Maximum of 2<superscript>32</superscript>-2 bytes, see <xref linkend="sect_6.2"/>
+  <emphasis>Note</emphasis> 2.</para><para>Leading <emphasis>inline</emphasis></para></td>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            element_text(&root),
+            "Maximum of 2^32-2 bytes, see Note 2.\nLeading inline"
+        );
+    }
+}
@@ -0,0 +1,541 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A concrete data element tag composed of a group number and an element
+/// number (e.g. "(0008,0001)").
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Tag {
+    /// The group number (e.g. 0x0008 for "(0008,0001)").
+    pub group: u16,
+
+    /// The element number (e.g. 0x0001 for "(0008,0001)").
+    pub element: u16,
+}
+
+impl Tag {
+    pub fn new(group: u16, element: u16) -> Self {
+        Tag { group, element }
+    }
+
+    /// Returns whether the tag belongs to a private data element, i.e. its
+    /// group is odd and not one of the groups 0001, 0003, 0005, 0007 and
+    /// FFFF which shall not be used.
+    pub fn is_private(&self) -> bool {
+        self.group % 2 == 1 && ![0x0001, 0x0003, 0x0005, 0x0007, 0xFFFF].contains(&self.group)
+    }
+
+    /// Returns whether the tag is a private creator data element (gggg,0010-
+    /// 00FF) reserving a block of a private group.
+    pub fn is_private_creator(&self) -> bool {
+        self.is_private() && (0x0010..=0x00FF).contains(&self.element)
+    }
+
+    /// Returns whether the tag is a group length (gggg,0000).
+    pub fn is_group_length(&self) -> bool {
+        self.element == 0x0000
+    }
+
+    /// Returns whether the tag belongs to the command group 0000 defined in
+    /// part 7.
+    pub fn is_command(&self) -> bool {
+        self.group == 0x0000
+    }
+
+    /// Returns whether the tag belongs to the file meta information group
+    /// 0002.
+    pub fn is_file_meta(&self) -> bool {
+        self.group == 0x0002
+    }
+
+    /// Returns whether the tag belongs to the directory structuring group
+    /// 0004.
+    pub fn is_directory_structuring(&self) -> bool {
+        self.group == 0x0004
+    }
+
+    /// Returns whether the tag belongs to one of the repeating groups of
+    /// curves (5000-501E), overlays (6000-601E) or variable pixel data
+    /// (7F00-7F1E).
+    pub fn is_repeating_group(&self) -> bool {
+        self.repeating_group_index().is_some()
+    }
+
+    /// Returns the index of the repeating group of curves, overlays or
+    /// variable pixel data the tag belongs to (e.g. 1 for (6002,3000)).
+    pub fn repeating_group_index(&self) -> Option<u16> {
+        let base = self.group & 0xFF00;
+        let offset = self.group & 0x00FF;
+        if (base == 0x5000 || base == 0x6000 || base == 0x7F00)
+            && offset.is_multiple_of(2)
+            && offset <= 0x1E
+        {
+            Some(offset / 2)
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether the tag is Item (FFFE,E000).
+    pub fn is_item(&self) -> bool {
+        *self == Tag::new(0xFFFE, 0xE000)
+    }
+
+    /// Returns whether the tag is Item Delimitation Item (FFFE,E00D) or
+    /// Sequence Delimitation Item (FFFE,E0DD).
+    pub fn is_delimitation(&self) -> bool {
+        self.group == 0xFFFE && (self.element == 0xE00D || self.element == 0xE0DD)
+    }
+}
+
+/// The class of a tag as determined by `Registry::classify`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TagClass {
+    /// An item or delimitation item (FFFE,xxxx) of an encoded sequence.
+    ItemOrDelimitation,
+
+    /// A group length (gggg,0000).
+    GroupLength,
+
+    /// A private creator element (gggg,0010-00FF) of an odd group.
+    PrivateCreator,
+
+    /// Any other element of an odd group.
+    Private,
+
+    /// A command element of part 7.
+    Command,
+
+    /// A file meta element.
+    FileMeta,
+
+    /// A directory structuring element.
+    DirectoryStructuring,
+
+    /// A data element of the data dictionary, including repeating groups.
+    Data,
+
+    /// A tag of a public group unknown to the registries.
+    Unknown,
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:04X},{:04X})", self.group, self.element)
+    }
+}
+
+impl FromStr for Tag {
+    type Err = Box<dyn Error>;
+
+    /// Parses a tag in the format "(gggg,eeee)". Tags defining ranges (e.g.
+    /// "(60xx,3000)") are rejected, use `TagRange` for those.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let range = s.parse::<TagRange>()?;
+        if range.is_range() {
+            return Err(From::from(format!("Tag '{}' defines a range of tags", s)));
+        }
+
+        Ok(Tag::new(range.group, range.element))
+    }
+}
+
+/// A tag as given in the data dictionary, which may stand for a whole range
+/// of tags (e.g. "(60xx,3000)" or "(1000,xxx0)").
+///
+/// Each hexadecimal digit of group and element is either fixed or a wildcard
+/// (written as "x"). A bit set in `group_mask`/`element_mask` marks the
+/// corresponding bit of `group`/`element` as fixed; wildcard digits have their
+/// mask and value bits cleared.
+///
+/// Ranges that cannot be written with wildcards, like the odd groups
+/// "0009-o-FFFF" or the elements "0010-u-00FF" of DCMTK's dicom.dic, are
+/// additionally limited by inclusive bounds. Their odd numbers have only the
+/// lowest bit fixed, their unrestricted numbers no bit at all.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct TagRange {
+    /// The fixed bits of the group number.
+    pub group: u16,
+
+    /// The mask of fixed bits of the group number.
+    pub group_mask: u16,
+
+    /// The fixed bits of the element number.
+    pub element: u16,
+
+    /// The mask of fixed bits of the element number.
+    pub element_mask: u16,
+
+    /// The inclusive lower and upper bounds of the group number, if the
+    /// groups cannot be written with wildcards (e.g. "0009-o-FFFF").
+    pub group_bounds: Option<(u16, u16)>,
+
+    /// The inclusive lower and upper bounds of the element number, if the
+    /// elements cannot be written with wildcards (e.g. "0010-u-00FF").
+    pub element_bounds: Option<(u16, u16)>,
+}
+
+impl TagRange {
+    /// Creates a `TagRange` standing for exactly the given `tag`.
+    pub fn single(tag: Tag) -> Self {
+        TagRange {
+            group: tag.group,
+            group_mask: 0xFFFF,
+            element: tag.element,
+            element_mask: 0xFFFF,
+            group_bounds: None,
+            element_bounds: None,
+        }
+    }
+
+    /// Creates a `TagRange` from inclusive lower and upper bounds of group
+    /// and element numbers as used by dictionaries like the one of DCMTK
+    /// (e.g. "(6000-60FF,3000)").
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the bounds cannot be expressed with wildcard
+    /// digits, i.e. if a digit differs between the bounds without being "0"
+    /// in the lower and "F" in the upper bound.
+    pub fn from_bounds(lower: Tag, upper: Tag) -> Result<Self, Box<dyn Error>> {
+        let (group, group_mask) = Self::mask_from_bounds(lower.group, upper.group)?;
+        let (element, element_mask) = Self::mask_from_bounds(lower.element, upper.element)?;
+        Ok(TagRange {
+            group,
+            group_mask,
+            element,
+            element_mask,
+            group_bounds: None,
+            element_bounds: None,
+        })
+    }
+
+    /// Returns whether this stands for more than one tag.
+    pub fn is_range(&self) -> bool {
+        self.group_mask != 0xFFFF || self.element_mask != 0xFFFF
+    }
+
+    /// Returns whether `tag` is covered by this range.
+    pub fn contains(&self, tag: Tag) -> bool {
+        let within = |number, bounds: Option<(u16, u16)>| {
+            bounds.is_none_or(|(lower, upper)| lower <= number && number <= upper)
+        };
+
+        tag.group & self.group_mask == self.group
+            && tag.element & self.element_mask == self.element
+            && within(tag.group, self.group_bounds)
+            && within(tag.element, self.element_bounds)
+    }
+
+    /// Returns all concrete tags covered by this range in ascending order.
+    ///
+    /// Wildcard groups only stand for even groups, as odd groups are private.
+    /// The repeating groups of curves, overlays and variable pixel data (e.g.
+    /// "(60xx,3000)" or "(7Fxx,0010)") are further limited to the groups
+    /// 5000-501E, 6000-601E and 7F00-7F1E as defined in section 7.6 of part 5.
+    /// Bounded ranges (e.g. "(0009-o-FFFF,0010-u-00FF)") cover every number
+    /// of the given kind within their bounds.
+    pub fn tags(&self) -> Vec<Tag> {
+        let mut groups = Self::expand_number(self.group, self.group_mask, self.group_bounds);
+        if self.group_mask != 0xFFFF && self.group_bounds.is_none() {
+            let repeating =
+                self.group_mask == 0xFF00 && Tag::new(self.group, 0).is_repeating_group();
+            groups.retain(|&group| {
+                group.is_multiple_of(2) && (!repeating || Tag::new(group, 0).is_repeating_group())
+            });
+        }
+
+        let elements = Self::expand_number(self.element, self.element_mask, self.element_bounds);
+        groups
+            .iter()
+            .flat_map(|&group| {
+                elements
+                    .iter()
+                    .map(move |&element| Tag::new(group, element))
+            })
+            .collect()
+    }
+
+    /// Returns all numbers matching `value` in the bits set in `mask` within
+    /// `bounds`.
+    fn expand_number(value: u16, mask: u16, bounds: Option<(u16, u16)>) -> Vec<u16> {
+        let (lower, upper) = bounds.unwrap_or((0, 0xFFFF));
+        let free = !mask;
+        let mut numbers = Vec::new();
+        let mut bits: u16 = 0;
+        loop {
+            let number = value | bits;
+            if lower <= number && number <= upper {
+                numbers.push(number);
+            }
+            if bits == free {
+                break;
+            }
+            // next combination of the wildcard bits in ascending order
+            bits = bits.wrapping_sub(free) & free;
+        }
+
+        numbers
+    }
+
+    fn mask_from_bounds(lower: u16, upper: u16) -> Result<(u16, u16), Box<dyn Error>> {
+        let mut value = 0;
+        let mut mask = 0;
+        for shift in (0..4).map(|digit| digit * 4) {
+            let lower_digit = (lower >> shift) & 0xF;
+            let upper_digit = (upper >> shift) & 0xF;
+            if lower_digit == upper_digit {
+                value |= lower_digit << shift;
+                mask |= 0xF << shift;
+            } else if lower_digit != 0 || upper_digit != 0xF {
+                return Err(From::from(format!(
+                    "Range {:04X}-{:04X} cannot be expressed with wildcards",
+                    lower, upper
+                )));
+            }
+        }
+
+        Ok((value, mask))
+    }
+
+    /// Parses a group or element number, which is either four digits that
+    /// may be wildcards (e.g. "60xx") or a range of DCMTK's dicom.dic (e.g.
+    /// "6000-60FF", "0009-o-FFFF" or "0010-u-00FF"), into its value, mask and
+    /// bounds.
+    fn parse_number(s: &str) -> Result<Number, Box<dyn Error>> {
+        let parts: Vec<&str> = s.split('-').collect();
+        let (lower, upper) = match parts.len() {
+            1 => {
+                let (value, mask) = Self::parse_digits(s)?;
+                return Ok((value, mask, None));
+            }
+            2 => {
+                let (value, mask) = Self::mask_from_bounds(
+                    Self::parse_bound(parts[0])?,
+                    Self::parse_bound(parts[1])?,
+                )?;
+                return Ok((value, mask, None));
+            }
+            3 => (Self::parse_bound(parts[0])?, Self::parse_bound(parts[2])?),
+            _ => return Err(From::from(format!("Invalid tag number '{}'", s))),
+        };
+        if lower > upper {
+            return Err(From::from(format!("Invalid range '{}'", s)));
+        }
+
+        match parts[1] {
+            "o" => Ok((1, 1, Some((lower, upper)))),
+            "u" => Ok(match Self::mask_from_bounds(lower, upper) {
+                Ok((value, mask)) => (value, mask, None),
+                Err(_) => (0, 0, Some((lower, upper))),
+            }),
+            _ => Err(From::from(format!("Invalid range '{}'", s))),
+        }
+    }
+
+    fn parse_bound(s: &str) -> Result<u16, Box<dyn Error>> {
+        match Self::parse_digits(s)? {
+            (value, 0xFFFF) => Ok(value),
+            _ => Err(From::from(format!("Invalid range bound '{}'", s))),
+        }
+    }
+
+    fn parse_digits(s: &str) -> Result<(u16, u16), Box<dyn Error>> {
+        if s.len() != 4 {
+            return Err(From::from(format!("Invalid tag number '{}'", s)));
+        }
+
+        let mut value = 0;
+        let mut mask = 0;
+        for c in s.chars() {
+            value <<= 4;
+            mask <<= 4;
+            if c == 'x' || c == 'X' {
+                continue;
+            }
+
+            match c.to_digit(16) {
+                Some(digit) => {
+                    value |= digit as u16;
+                    mask |= 0xF;
+                }
+                None => return Err(From::from(format!("Invalid tag number '{}'", s))),
+            }
+        }
+
+        Ok((value, mask))
+    }
+
+    fn format_number(
+        f: &mut fmt::Formatter,
+        value: u16,
+        mask: u16,
+        bounds: Option<(u16, u16)>,
+    ) -> fmt::Result {
+        if let Some((lower, upper)) = bounds {
+            let kind = if mask == 0 { "u" } else { "o" };
+            return write!(f, "{:04X}-{}-{:04X}", lower, kind, upper);
+        }
+
+        for shift in (0..4).rev().map(|digit| digit * 4) {
+            if (mask >> shift) & 0xF == 0 {
+                write!(f, "x")?;
+            } else {
+                write!(f, "{:X}", (value >> shift) & 0xF)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The value, mask and bounds of a group or element number of a `TagRange`.
+type Number = (u16, u16, Option<(u16, u16)>);
+
+impl From<Tag> for TagRange {
+    fn from(tag: Tag) -> Self {
+        TagRange::single(tag)
+    }
+}
+
+impl fmt::Display for TagRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        Self::format_number(f, self.group, self.group_mask, self.group_bounds)?;
+        write!(f, ",")?;
+        Self::format_number(f, self.element, self.element_mask, self.element_bounds)?;
+        write!(f, ")")
+    }
+}
+
+impl FromStr for TagRange {
+    type Err = Box<dyn Error>;
+
+    /// Parses a tag in the format "(gggg,eeee)" where each digit may be
+    /// replaced by "x" (e.g. "(60xx,3000)"). Group and element may also be
+    /// ranges as used by DCMTK's dicom.dic (e.g. "(6000-60FF,3000)" or
+    /// "(0009-o-FFFF,0010-u-00FF)").
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        if !trimmed.starts_with('(') || !trimmed.ends_with(')') {
+            return Err(From::from(format!("Invalid tag '{}'", s)));
+        }
+
+        let mut numbers = trimmed[1..trimmed.len() - 1].split(',');
+        let (group, element) = match (numbers.next(), numbers.next(), numbers.next()) {
+            (Some(group), Some(element), None) => (group.trim(), element.trim()),
+            _ => return Err(From::from(format!("Invalid tag '{}'", s))),
+        };
+
+        let (group, group_mask, group_bounds) = Self::parse_number(group)?;
+        let (element, element_mask, element_bounds) = Self::parse_number(element)?;
+        Ok(TagRange {
+            group,
+            group_mask,
+            element,
+            element_mask,
+            group_bounds,
+            element_bounds,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_range_round_trips_wildcards() {
+        let range = "(60xx,3000)".parse::<TagRange>().unwrap();
+        assert_eq!(range.group_mask, 0xFF00);
+        assert_eq!(range.to_string(), "(60xx,3000)");
+        assert!(range.contains(Tag::new(0x6002, 0x3000)));
+        assert!(!range.contains(Tag::new(0x6102, 0x3000)));
+    }
+
+    #[test]
+    fn tags_expand_wildcards_by_repeating_group_rules() {
+        let overlay_data = "(60xx,3000)".parse::<TagRange>().unwrap().tags();
+        assert_eq!(overlay_data.len(), 16);
+        assert_eq!(overlay_data[1], Tag::new(0x6002, 0x3000));
+        assert_eq!(overlay_data[15], Tag::new(0x601E, 0x3000));
+
+        let variable_pixel_data = "(7Fxx,0010)".parse::<TagRange>().unwrap().tags();
+        assert_eq!(variable_pixel_data.len(), 16);
+        assert_eq!(variable_pixel_data[15], Tag::new(0x7F1E, 0x0010));
+
+        let escape_triplet = "(1000,xxx0)".parse::<TagRange>().unwrap().tags();
+        assert_eq!(escape_triplet.len(), 4096);
+        assert_eq!(escape_triplet[1], Tag::new(0x1000, 0x0010));
+
+        let source_image_ids = "(0020,31xx)".parse::<TagRange>().unwrap().tags();
+        assert_eq!(source_image_ids.len(), 256);
+        assert_eq!(
+            TagRange::single(Tag::new(0x0010, 0x0010)).tags(),
+            vec![Tag::new(0x0010, 0x0010)]
+        );
+    }
+
+    #[test]
+    fn tag_range_supports_odd_and_unrestricted_bounds() {
+        let private_creators = "(0009-o-FFFF,0010-u-00FF)".parse::<TagRange>().unwrap();
+        assert_eq!(private_creators.to_string(), "(0009-o-FFFF,0010-u-00FF)");
+        assert!(private_creators.is_range());
+        assert!(private_creators.contains(Tag::new(0x0029, 0x0010)));
+        assert!(private_creators.contains(Tag::new(0xFFFF, 0x00FF)));
+        assert!(!private_creators.contains(Tag::new(0x0028, 0x0010)));
+        assert!(!private_creators.contains(Tag::new(0x0007, 0x0010)));
+        assert!(!private_creators.contains(Tag::new(0x0029, 0x000F)));
+        assert!(!private_creators.contains(Tag::new(0x0029, 0x0100)));
+
+        let illegal = "(0001-o-0007,0000)".parse::<TagRange>().unwrap().tags();
+        assert_eq!(
+            illegal,
+            vec![
+                Tag::new(0x0001, 0),
+                Tag::new(0x0003, 0),
+                Tag::new(0x0005, 0),
+                Tag::new(0x0007, 0)
+            ]
+        );
+
+        let generic = "(0000-u-FFFF,0000)".parse::<TagRange>().unwrap();
+        assert_eq!(generic.to_string(), "(xxxx,0000)");
+        assert!("(0009-x-FFFF,0000)".parse::<TagRange>().is_err());
+        assert!("(FFFF-o-0009,0000)".parse::<TagRange>().is_err());
+    }
+
+    #[test]
+    fn tag_classification_helpers() {
+        assert!(Tag::new(0x0009, 0x0010).is_private_creator());
+        assert!(Tag::new(0x0009, 0x1010).is_private());
+        assert!(!Tag::new(0x0009, 0x1010).is_private_creator());
+        assert!(!Tag::new(0x0007, 0x0010).is_private());
+        assert!(Tag::new(0x0018, 0x0000).is_group_length());
+        assert!(Tag::new(0x0000, 0x0900).is_command());
+        assert!(Tag::new(0x0002, 0x0010).is_file_meta());
+        assert!(Tag::new(0x0004, 0x1220).is_directory_structuring());
+        assert_eq!(Tag::new(0x6002, 0x3000).repeating_group_index(), Some(1));
+        assert!(!Tag::new(0x6020, 0x3000).is_repeating_group());
+        assert!(!Tag::new(0x6001, 0x0010).is_repeating_group());
+        assert!(Tag::new(0xFFFE, 0xE000).is_item());
+        assert!(Tag::new(0xFFFE, 0xE0DD).is_delimitation());
+    }
+
+    #[test]
+    fn tag_rejects_ranges() {
+        assert_eq!(
+            "(7fe0,0010)".parse::<Tag>().unwrap(),
+            Tag::new(0x7FE0, 0x0010)
+        );
+        assert!("(1000,xxx0)".parse::<Tag>().is_err());
+    }
+
+    #[test]
+    fn tag_range_from_bounds_uses_wildcards_for_full_digits() {
+        let range =
+            TagRange::from_bounds(Tag::new(0x6000, 0x0010), Tag::new(0x60FF, 0x0010)).unwrap();
+        assert_eq!(range.to_string(), "(60xx,0010)");
+        assert!(TagRange::from_bounds(Tag::new(0x6000, 0), Tag::new(0x601E, 0)).is_err());
+    }
+}
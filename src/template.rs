@@ -0,0 +1,280 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use code::CodedConcept;
+use uid::TemplateUID;
+
+/// The value type of a content item of a structured report.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ValueType {
+    Container,
+    Text,
+    Code,
+    Num,
+    DateTime,
+    Date,
+    Time,
+    UidRef,
+    PName,
+    Composite,
+    Image,
+    Waveform,
+    SCoord,
+    SCoord3D,
+    TCoord,
+
+    /// The content items of another template ("INCLUDE").
+    Include,
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            ValueType::Container => "CONTAINER",
+            ValueType::Text => "TEXT",
+            ValueType::Code => "CODE",
+            ValueType::Num => "NUM",
+            ValueType::DateTime => "DATETIME",
+            ValueType::Date => "DATE",
+            ValueType::Time => "TIME",
+            ValueType::UidRef => "UIDREF",
+            ValueType::PName => "PNAME",
+            ValueType::Composite => "COMPOSITE",
+            ValueType::Image => "IMAGE",
+            ValueType::Waveform => "WAVEFORM",
+            ValueType::SCoord => "SCOORD",
+            ValueType::SCoord3D => "SCOORD3D",
+            ValueType::TCoord => "TCOORD",
+            ValueType::Include => "INCLUDE",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for ValueType {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "CONTAINER" => Ok(ValueType::Container),
+            "TEXT" => Ok(ValueType::Text),
+            "CODE" => Ok(ValueType::Code),
+            "NUM" => Ok(ValueType::Num),
+            "DATETIME" => Ok(ValueType::DateTime),
+            "DATE" => Ok(ValueType::Date),
+            "TIME" => Ok(ValueType::Time),
+            "UIDREF" => Ok(ValueType::UidRef),
+            "PNAME" => Ok(ValueType::PName),
+            "COMPOSITE" => Ok(ValueType::Composite),
+            "IMAGE" => Ok(ValueType::Image),
+            "WAVEFORM" => Ok(ValueType::Waveform),
+            "SCOORD" => Ok(ValueType::SCoord),
+            "SCOORD3D" => Ok(ValueType::SCoord3D),
+            "TCOORD" => Ok(ValueType::TCoord),
+            "INCLUDE" => Ok(ValueType::Include),
+            _ => Err(From::from(format!("Unknown value type '{}'", s))),
+        }
+    }
+}
+
+/// The requirement type of a content item within a template.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RequirementType {
+    /// The content item is mandatory ("M").
+    Mandatory,
+
+    /// The content item is mandatory if the condition is met ("MC").
+    MandatoryConditional,
+
+    /// The content item is optional ("U").
+    UserOption,
+
+    /// The content item may only be present if the condition is met ("UC").
+    UserOptionConditional,
+}
+
+impl fmt::Display for RequirementType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            RequirementType::Mandatory => "M",
+            RequirementType::MandatoryConditional => "MC",
+            RequirementType::UserOption => "U",
+            RequirementType::UserOptionConditional => "UC",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for RequirementType {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "M" => Ok(RequirementType::Mandatory),
+            "MC" => Ok(RequirementType::MandatoryConditional),
+            "U" => Ok(RequirementType::UserOption),
+            "UC" => Ok(RequirementType::UserOptionConditional),
+            _ => Err(From::from(format!("Unknown requirement type '{}'", s))),
+        }
+    }
+}
+
+/// A row of a template table, including the rows nested below it.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TemplateItem {
+    /// The relationship with the parent content item (e.g. "CONTAINS" or
+    /// "HAS CONCEPT MOD"), empty for the root of a template.
+    pub relationship: String,
+
+    /// The value type of the content item, `None` if it is unknown.
+    pub value_type: Option<ValueType>,
+
+    /// The concept name as given in the table (e.g. "EV (126000, DCM,
+    /// "Imaging Measurement Report")"), for "INCLUDE" rows including the
+    /// number of the linked template (e.g. "DTID 1204").
+    pub concept_name: String,
+
+    /// The value multiplicity (e.g. "1" or "1-n").
+    pub vm: String,
+
+    /// The requirement type, `None` if it is unknown.
+    pub requirement_type: Option<RequirementType>,
+
+    /// The condition of a conditional content item, empty if there is none.
+    pub condition: String,
+
+    /// The value set constraint as given in the table, including the number
+    /// of the linked context group (e.g. "DCID 5000").
+    pub value_set_constraint: String,
+
+    /// The TID of the template included by an "INCLUDE" row.
+    pub included_template: Option<u32>,
+
+    /// The CID of the context group the value is constrained to, if any.
+    pub value_set_cid: Option<u32>,
+
+    /// The rows with the next higher nesting level below this row.
+    pub children: Vec<TemplateItem>,
+}
+
+impl TemplateItem {
+    /// Returns the coded concept name of the content item, if it is given as
+    /// an enumerated value or defined term (e.g. "EV (126000, DCM, "Imaging
+    /// Measurement Report")").
+    pub fn concept_name_code(&self) -> Option<CodedConcept> {
+        self.concept_name.parse().ok()
+    }
+}
+
+/// A template as defined in Annex A "Structured Reporting Templates" of part
+/// 16 (e.g. "TID 1500 Measurement Report").
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Template {
+    /// The template identifier (e.g. 1500 for "TID 1500").
+    pub tid: u32,
+
+    /// The name of the template (e.g. "Measurement Report").
+    pub name: String,
+
+    /// Whether the template may be extended with further content items.
+    pub extensible: bool,
+
+    /// Whether the order of the content items is significant.
+    pub order_significant: bool,
+
+    /// Whether the template may be the root of a structured report.
+    pub root: bool,
+
+    /// The rows of the table with nesting level 0.
+    pub items: Vec<TemplateItem>,
+}
+
+/// All templates of part 16 keyed by their TID.
+#[derive(Clone, Debug, Default)]
+pub struct TemplateRegistry {
+    templates: BTreeMap<u32, Template>,
+}
+
+impl TemplateRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new `TemplateRegistry` containing `templates`.
+    pub fn from_templates<I>(templates: I) -> Self
+    where
+        I: IntoIterator<Item = Template>,
+    {
+        TemplateRegistry {
+            templates: templates
+                .into_iter()
+                .map(|template| (template.tid, template))
+                .collect(),
+        }
+    }
+
+    /// Returns the template with the given `tid`.
+    pub fn get(&self, tid: u32) -> Option<&Template> {
+        self.templates.get(&tid)
+    }
+
+    /// Returns the template of a UID listed in the "Template UID Values"
+    /// table of part 6.
+    pub fn resolve(&self, uid: &TemplateUID) -> Option<&Template> {
+        uid.tid().and_then(|tid| self.get(tid))
+    }
+
+    /// Returns all rows of the template `tid` together with their nesting
+    /// level, with all "INCLUDE" rows replaced by the rows of the included
+    /// template at the nesting level of the "INCLUDE" row. Includes of unknown
+    /// templates are kept.
+    pub fn items(&self, tid: u32) -> Vec<(usize, &TemplateItem)> {
+        let mut items = Vec::new();
+        if let Some(template) = self.templates.get(&tid) {
+            self.collect_items(&template.items, 0, &mut vec![tid], &mut items);
+        }
+        items
+    }
+
+    /// Returns all templates ordered by TID.
+    pub fn templates(&self) -> impl Iterator<Item = &Template> {
+        self.templates.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    fn collect_items<'a>(
+        &'a self,
+        items: &'a [TemplateItem],
+        depth: usize,
+        visited: &mut Vec<u32>,
+        collected: &mut Vec<(usize, &'a TemplateItem)>,
+    ) {
+        for item in items {
+            let included = item
+                .included_template
+                .and_then(|tid| self.templates.get(&tid));
+            match included {
+                // guard against templates (indirectly) including themselves
+                Some(template) if !visited.contains(&template.tid) => {
+                    visited.push(template.tid);
+                    self.collect_items(&template.items, depth, visited, collected);
+                    visited.pop();
+                }
+                Some(_) => {}
+                None => {
+                    collected.push((depth, item));
+                    self.collect_items(&item.children, depth + 1, visited, collected);
+                }
+            }
+        }
+    }
+}
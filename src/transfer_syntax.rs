@@ -0,0 +1,317 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use encoding::EncodingParser;
+use Kind;
+use UID;
+
+/// The byte order of the binary values of a transfer syntax.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ByteOrder {
+    LittleEndian,
+    BigEndian,
+}
+
+/// The family of compression used by an encapsulated transfer syntax.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CompressionFamily {
+    Jpeg,
+    JpegLs,
+    Jpeg2000,
+    HighThroughputJpeg2000,
+    JpegXl,
+    Rle,
+    Mpeg,
+}
+
+/// The properties of a transfer syntax relevant for encoding and decoding
+/// data sets.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferSyntax {
+    /// The UID of the transfer syntax.
+    pub uid: UID,
+
+    /// Whether the VR is encoded explicitly.
+    pub explicit_vr: bool,
+
+    /// The byte order of binary values.
+    pub byte_order: ByteOrder,
+
+    /// Whether pixel data is encapsulated (i.e. stored as a sequence of
+    /// fragments) instead of in its native format.
+    pub encapsulated: bool,
+
+    /// Whether the whole data set (and not only the pixel data) is compressed
+    /// with the deflate algorithm.
+    pub deflated: bool,
+
+    /// The family of compression of the pixel data, `None` for native or
+    /// uncompressed encapsulated pixel data.
+    pub compression: Option<CompressionFamily>,
+}
+
+impl TransferSyntax {
+    /// Returns the properties of the transfer syntax `uid` as derived from
+    /// its name, or `None` if `uid` is not a transfer syntax.
+    ///
+    /// All transfer syntaxes except "Implicit VR Little Endian" and the
+    /// retired "Explicit VR Big Endian" use explicit VR little endian
+    /// encoding as defined in section 10 of part 5. The pixel data of all
+    /// transfer syntaxes named after a compression (e.g. "JPEG Baseline
+    /// (Process 1)") or "Encapsulated" is encapsulated.
+    pub fn from_uid(uid: &UID) -> Option<Self> {
+        if uid.kind != Kind::TransferSyntax {
+            return None;
+        }
+
+        let name = uid.normalized_name.as_str();
+        let compression = Self::compression_family(name);
+        let referenced = name.starts_with("JPIP") && name.contains("Referenced");
+        Some(TransferSyntax {
+            uid: uid.clone(),
+            explicit_vr: !name.contains("Implicit VR"),
+            byte_order: if name.contains("Big Endian") {
+                ByteOrder::BigEndian
+            } else {
+                ByteOrder::LittleEndian
+            },
+            encapsulated: !referenced
+                && (compression.is_some() || name.starts_with("Encapsulated")),
+            deflated: name.starts_with("Deflated Explicit VR")
+                || (referenced && name.ends_with("Deflate")),
+            compression: if referenced { None } else { compression },
+        })
+    }
+
+    fn compression_family(name: &str) -> Option<CompressionFamily> {
+        if name.contains("High-Throughput JPEG 2000") || name.contains("HTJ2K") {
+            Some(CompressionFamily::HighThroughputJpeg2000)
+        } else if name.contains("JPEG 2000") || name.starts_with("JPIP") {
+            Some(CompressionFamily::Jpeg2000)
+        } else if name.starts_with("JPEG XL") {
+            Some(CompressionFamily::JpegXl)
+        } else if name.contains("JPEG-LS") {
+            Some(CompressionFamily::JpegLs)
+        } else if name.starts_with("JPEG") {
+            Some(CompressionFamily::Jpeg)
+        } else if name.starts_with("RLE") {
+            Some(CompressionFamily::Rle)
+        } else if name.contains("MPEG") || name.contains("HEVC") {
+            Some(CompressionFamily::Mpeg)
+        } else {
+            None
+        }
+    }
+}
+
+/// All transfer syntaxes of the UID registry keyed by their UID value.
+#[derive(Clone, Debug, Default)]
+pub struct TransferSyntaxRegistry {
+    transfer_syntaxes: BTreeMap<String, TransferSyntax>,
+}
+
+impl TransferSyntaxRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new `TransferSyntaxRegistry` from all transfer syntaxes in
+    /// `uids` (e.g. the result of `Parser::parse_unique_identifier_registry`).
+    pub fn from_uids(uids: &[UID]) -> Self {
+        TransferSyntaxRegistry {
+            transfer_syntaxes: uids
+                .iter()
+                .filter_map(TransferSyntax::from_uid)
+                .map(|ts| (ts.uid.value.clone(), ts))
+                .collect(),
+        }
+    }
+
+    /// Marks all transfer syntaxes listed in the section "Transfer Syntaxes
+    /// For Encapsulation of Encoded Pixel Data" of part 5 as encapsulated,
+    /// e.g. for transfer syntaxes whose name does not tell.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if parsing the section fails.
+    pub fn supplement(&mut self, parser: &EncodingParser) -> Result<(), Box<dyn Error>> {
+        for value in parser.parse_encapsulated_transfer_syntax_uids()? {
+            if let Some(transfer_syntax) = self.transfer_syntaxes.get_mut(&value) {
+                transfer_syntax.encapsulated = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the transfer syntax with the UID `value`.
+    pub fn get(&self, value: &str) -> Option<&TransferSyntax> {
+        self.transfer_syntaxes.get(value)
+    }
+
+    /// Returns all transfer syntaxes ordered by UID.
+    pub fn transfer_syntaxes(&self) -> impl Iterator<Item = &TransferSyntax> {
+        self.transfer_syntaxes.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.transfer_syntaxes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transfer_syntaxes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fixtures::fixture;
+
+    fn uid(value: &str, name: &str, kind: Kind) -> UID {
+        UID {
+            value: value.to_owned(),
+            full_name: name.to_owned(),
+            normalized_name: name.to_owned(),
+            kind,
+        }
+    }
+
+    fn registry() -> TransferSyntaxRegistry {
+        TransferSyntaxRegistry::from_uids(&[
+            uid(
+                "1.2.840.10008.1.1",
+                "Verification SOP Class",
+                Kind::SopClass,
+            ),
+            uid(
+                "1.2.840.10008.1.2",
+                "Implicit VR Little Endian",
+                Kind::TransferSyntax,
+            ),
+            uid(
+                "1.2.840.10008.1.2.1.99",
+                "Deflated Explicit VR Little Endian",
+                Kind::TransferSyntax,
+            ),
+            uid(
+                "1.2.840.10008.1.2.2",
+                "Explicit VR Big Endian",
+                Kind::TransferSyntax,
+            ),
+            uid(
+                "1.2.840.10008.1.2.4.50",
+                "JPEG Baseline (Process 1)",
+                Kind::TransferSyntax,
+            ),
+            uid(
+                "1.2.840.10008.1.2.4.80",
+                "JPEG-LS Lossless Image Compression",
+                Kind::TransferSyntax,
+            ),
+            uid(
+                "1.2.840.10008.1.2.4.95",
+                "JPIP Referenced Deflate",
+                Kind::TransferSyntax,
+            ),
+            uid(
+                "1.2.840.10008.1.2.4.201",
+                "High-Throughput JPEG 2000 Image Compression (Lossless Only)",
+                Kind::TransferSyntax,
+            ),
+            uid("1.2.840.10008.1.2.5", "RLE Lossless", Kind::TransferSyntax),
+        ])
+    }
+
+    #[test]
+    fn from_uids_derives_encoding_from_names() {
+        let registry = registry();
+        assert_eq!(registry.len(), 8);
+        assert!(registry.get("1.2.840.10008.1.1").is_none());
+
+        let implicit = registry.get("1.2.840.10008.1.2").unwrap();
+        assert!(!implicit.explicit_vr);
+        assert!(!implicit.encapsulated);
+        assert_eq!(implicit.byte_order, ByteOrder::LittleEndian);
+
+        let big_endian = registry.get("1.2.840.10008.1.2.2").unwrap();
+        assert!(big_endian.explicit_vr);
+        assert_eq!(big_endian.byte_order, ByteOrder::BigEndian);
+
+        let deflated = registry.get("1.2.840.10008.1.2.1.99").unwrap();
+        assert!(deflated.deflated);
+        assert!(!deflated.encapsulated);
+
+        let jpip = registry.get("1.2.840.10008.1.2.4.95").unwrap();
+        assert!(jpip.deflated);
+        assert!(!jpip.encapsulated);
+    }
+
+    #[test]
+    fn from_uids_derives_compression_family() {
+        let registry = registry();
+        let compression = |value| registry.get(value).unwrap().compression;
+        assert_eq!(
+            compression("1.2.840.10008.1.2.4.50"),
+            Some(CompressionFamily::Jpeg)
+        );
+        assert_eq!(
+            compression("1.2.840.10008.1.2.4.80"),
+            Some(CompressionFamily::JpegLs)
+        );
+        assert_eq!(
+            compression("1.2.840.10008.1.2.4.201"),
+            Some(CompressionFamily::HighThroughputJpeg2000)
+        );
+        assert_eq!(
+            compression("1.2.840.10008.1.2.5"),
+            Some(CompressionFamily::Rle)
+        );
+        assert!(registry.get("1.2.840.10008.1.2.5").unwrap().encapsulated);
+    }
+
+    #[test]
+    fn from_uids_derives_compression_family_of_fragmentable_video() {
+        let registry = TransferSyntaxRegistry::from_uids(&[
+            uid(
+                "1.2.840.10008.1.2.4.100.1",
+                "Fragmentable MPEG2 Main Profile / Main Level",
+                Kind::TransferSyntax,
+            ),
+            uid(
+                "1.2.840.10008.1.2.4.102.1",
+                "Fragmentable MPEG-4 AVC/H.264 High Profile / Level 4.1",
+                Kind::TransferSyntax,
+            ),
+        ]);
+        for transfer_syntax in registry.transfer_syntaxes() {
+            assert_eq!(transfer_syntax.compression, Some(CompressionFamily::Mpeg));
+            assert!(transfer_syntax.encapsulated);
+        }
+        assert_eq!(registry.len(), 2);
+    }
+
+    #[test]
+    fn supplement_marks_transfer_syntaxes_of_part_5_as_encapsulated() {
+        let mut registry = TransferSyntaxRegistry::from_uids(&[
+            uid(
+                "1.2.840.10008.1.2.4.50",
+                "JPEG Baseline (Process 1)",
+                Kind::TransferSyntax,
+            ),
+            uid(
+                "1.2.840.10008.1.2.8.1",
+                "Deflated Image Frame Compression",
+                Kind::TransferSyntax,
+            ),
+        ]);
+        let deflated_frames = registry.get("1.2.840.10008.1.2.8.1").unwrap();
+        assert!(!deflated_frames.encapsulated);
+        assert!(!deflated_frames.deflated);
+
+        let parser = EncodingParser::with_part5_file_contents(fixture(5).to_owned());
+        registry.supplement(&parser).unwrap();
+        assert!(registry.get("1.2.840.10008.1.2.8.1").unwrap().encapsulated);
+        assert!(registry.get("1.2.840.10008.1.2.4.50").unwrap().encapsulated);
+    }
+}
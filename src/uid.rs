@@ -53,4 +53,77 @@ impl UID {
             kind: Kind::TransferSyntax,
         }
     }
+
+    /// Returns whether the UID is retired (i.e. its full name contains
+    /// "(Retired)").
+    pub fn is_retired(&self) -> bool {
+        self.full_name.contains("(Retired)")
+    }
+}
+
+/// A well-known frame of reference as defined in the "Well-known Frames of
+/// Reference" table of the DICOM standard.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct FrameOfReference {
+    /// The value of the UID (e.g. "1.2.840.10008.1.4.1.1")
+    pub value: String,
+
+    /// The name of the frame of reference (e.g. "Talairach Brain Atlas Frame
+    /// of Reference")
+    pub name: String,
+
+    /// The reference to the publication defining the frame of reference
+    pub normative_reference: String,
+}
+
+/// The UID of a context group as defined in the "Context Group UID Values"
+/// table of the DICOM standard.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ContextGroupUID {
+    /// The value of the UID (e.g. "1.2.840.10008.6.1.1")
+    pub value: String,
+
+    /// The identifier of the context group as given in the DICOM Standard
+    /// (e.g. "CID 2")
+    pub context_identifier: String,
+
+    /// The name of the context group (e.g. "Anatomic Modifier")
+    pub name: String,
+}
+
+impl ContextGroupUID {
+    /// Returns the number of the context group (e.g. 2 for "CID 2").
+    pub fn cid(&self) -> Option<u32> {
+        self.context_identifier
+            .trim_start_matches("CID")
+            .trim()
+            .parse()
+            .ok()
+    }
+}
+
+/// The UID of a template as defined in the "Template UID Values" table of the
+/// DICOM standard.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TemplateUID {
+    /// The value of the UID (e.g. "1.2.840.10008.8.1.1")
+    pub value: String,
+
+    /// The identifier of the template as given in the DICOM Standard (e.g.
+    /// "TID 1500")
+    pub template_identifier: String,
+
+    /// The name of the template (e.g. "Measurement Report")
+    pub name: String,
+}
+
+impl TemplateUID {
+    /// Returns the number of the template (e.g. 1500 for "TID 1500").
+    pub fn tid(&self) -> Option<u32> {
+        self.template_identifier
+            .trim_start_matches("TID")
+            .trim()
+            .parse()
+            .ok()
+    }
 }
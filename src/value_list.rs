@@ -0,0 +1,281 @@
+use std::collections::{BTreeMap, HashMap};
+
+use table;
+use tag::{Tag, TagRange};
+
+use xmltree;
+
+/// Whether the values of a list are the only values allowed or just the
+/// values defined so far.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ValueListKind {
+    /// Only the listed values are allowed ("Enumerated Values").
+    EnumeratedValues,
+
+    /// The listed values may be extended ("Defined Terms").
+    DefinedTerms,
+}
+
+impl ValueListKind {
+    /// Returns the kind of list introduced by `title` (e.g. "Enumerated
+    /// Values:" or "Defined Terms:").
+    fn from_title(title: &str) -> Option<Self> {
+        let title = title.trim();
+        if title.starts_with("Enumerated Value") {
+            Some(ValueListKind::EnumeratedValues)
+        } else if title.starts_with("Defined Term") {
+            Some(ValueListKind::DefinedTerms)
+        } else {
+            None
+        }
+    }
+}
+
+/// A single value of a list (e.g. "M" meaning "male").
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Term {
+    /// The value as it is encoded (e.g. "M").
+    pub value: String,
+
+    /// The meaning of the value (e.g. "male"), empty if not given.
+    pub meaning: String,
+}
+
+/// The enumerated values or defined terms of an attribute within a single
+/// module or macro.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValueList {
+    /// The tag of the attribute (e.g. "(0010,0040)").
+    pub tag: String,
+
+    /// Whether the values are enumerated values or defined terms.
+    pub kind: ValueListKind,
+
+    /// The caption of the table of the module or macro the list applies in
+    /// (e.g. "Patient Module Attributes").
+    pub module: String,
+
+    /// The xml:id of the table of the module or macro (e.g. "table_C.7-1").
+    pub table_id: String,
+
+    /// The values of the list.
+    pub terms: Vec<Term>,
+}
+
+impl ValueList {
+    /// Returns whether `value` is one of the values of the list.
+    pub fn contains(&self, value: &str) -> bool {
+        self.terms.iter().any(|term| term.value == value)
+    }
+}
+
+/// All enumerated values and defined terms of part 3 keyed by the tag of
+/// their attribute.
+#[derive(Clone, Debug, Default)]
+pub struct ValueListRegistry {
+    lists: BTreeMap<TagRange, Vec<ValueList>>,
+}
+
+impl ValueListRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new `ValueListRegistry` from `lists`. Lists whose tag is not
+    /// in the format "(gggg,eeee)" are skipped.
+    pub fn from_lists<I>(lists: I) -> Self
+    where
+        I: IntoIterator<Item = ValueList>,
+    {
+        let mut registry = ValueListRegistry::new();
+        for list in lists {
+            if let Ok(tag) = list.tag.parse() {
+                registry.lists.entry(tag).or_default().push(list);
+            }
+        }
+
+        registry
+    }
+
+    /// Returns the lists of the attribute `tag` in all modules and macros.
+    pub fn lookup(&self, tag: Tag) -> &[ValueList] {
+        self.lists
+            .get(&TagRange::single(tag))
+            .map(|lists| lists.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Returns the list of the attribute `tag` within the module or macro
+    /// whose table has the xml:id `table_id`.
+    pub fn lookup_in(&self, tag: Tag, table_id: &str) -> Option<&ValueList> {
+        self.lookup(tag)
+            .iter()
+            .find(|list| list.table_id == table_id)
+    }
+
+    /// Returns the tags and lists of all attributes ordered by tag.
+    pub fn lists(&self) -> impl Iterator<Item = (&TagRange, &[ValueList])> {
+        self.lists
+            .iter()
+            .map(|(tag, lists)| (tag, lists.as_slice()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.lists.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lists.is_empty()
+    }
+}
+
+/// Returns the value lists in the rows of the attribute `table` with the
+/// given `caption` and xml:id `table_id`. The tag of each row is taken from
+/// the first cell containing a tag, the lists from its last cell.
+///
+/// Lists given in an "Attribute Descriptions" section (e.g. C.7.3.1.1.1
+/// "Modality") are taken from the `sections` linked in the last cell, if the
+/// title of the section is the name of the attribute or its text mentions the
+/// tag of the attribute.
+pub(crate) fn value_lists(
+    table: &xmltree::Element,
+    caption: &str,
+    table_id: &str,
+    sections: &HashMap<&str, &xmltree::Element>,
+) -> Vec<ValueList> {
+    let mut lists = Vec::new();
+    let rows = table
+        .children
+        .iter()
+        .filter(|c| c.name == "tbody")
+        .flat_map(|tbody| tbody.children.iter().filter(|c| c.name == "tr"));
+    for tr in rows {
+        let cells: Vec<&xmltree::Element> = tr.children.iter().filter(|c| c.name == "td").collect();
+        let tag = cells
+            .iter()
+            .map(|&td| table::element_text(td))
+            .find(|text| text.parse::<TagRange>().is_ok());
+        let (tag, description) = match (tag, cells.last()) {
+            (Some(tag), Some(&description)) => (tag, description),
+            _ => continue,
+        };
+
+        let mut variable_lists = Vec::new();
+        collect_variable_lists(description, &mut variable_lists);
+
+        let name = table::element_text(cells[0]);
+        let name = name.trim_start_matches('>').trim();
+        for link in table::element_links(description) {
+            let section = match sections.get(link.as_str()) {
+                Some(section) => section,
+                None => continue,
+            };
+            let title = section
+                .get_child("title")
+                .map(table::element_text)
+                .unwrap_or_default();
+            if title.eq_ignore_ascii_case(name) || table::element_text(section).contains(&tag) {
+                collect_variable_lists(section, &mut variable_lists);
+            }
+        }
+
+        for (kind, terms) in variable_lists {
+            lists.push(ValueList {
+                tag: tag.clone(),
+                kind,
+                module: caption.to_owned(),
+                table_id: table_id.to_owned(),
+                terms,
+            });
+        }
+    }
+
+    lists
+}
+
+/// Collects the kind and terms of all "variablelist" elements in `element`
+/// except those of its sub-sections. The kind is taken from the title of the
+/// list or, if it has none, from the paragraph preceding it (e.g. "Enumerated
+/// Values:").
+fn collect_variable_lists(element: &xmltree::Element, lists: &mut Vec<(ValueListKind, Vec<Term>)>) {
+    let mut preceding_kind = None;
+    for child in &element.children {
+        if child.name == "section" {
+            continue;
+        }
+
+        if child.name != "variablelist" {
+            preceding_kind = ValueListKind::from_title(&table::element_text(child));
+            collect_variable_lists(child, lists);
+            continue;
+        }
+
+        let kind = child
+            .get_child("title")
+            .and_then(|title| ValueListKind::from_title(&table::element_text(title)))
+            .or(preceding_kind);
+        if let Some(kind) = kind {
+            let terms = child
+                .children
+                .iter()
+                .filter(|c| c.name == "varlistentry")
+                .map(|entry| Term {
+                    value: entry
+                        .get_child("term")
+                        .map(table::element_text)
+                        .unwrap_or_default(),
+                    meaning: entry
+                        .get_child("listitem")
+                        .map(table::element_text)
+                        .unwrap_or_default(),
+                })
+                .collect();
+            lists.push((kind, terms));
+        }
+        preceding_kind = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use document::parse_xml;
+
+    #[test]
+    fn value_lists_read_title_or_preceding_paragraph() {
+        let table = parse_xml(
+            r#"<table><tbody>
+  <tr>
+    <td><para>Patient's Sex</para></td><td><para>(0010,0040)</para></td><td><para>2</para></td>
+    <td><para>Sex of the named Patient.</para><para>Enumerated Values:</para>
+      <variablelist><varlistentry><term>M</term><listitem><para>male</para></listitem></varlistentry>
+      <varlistentry><term>F</term><listitem><para>female</para></listitem></varlistentry></variablelist></td>
+  </tr>
+  <tr>
+    <td><para>Modality</para></td><td><para>(0008,0060)</para></td><td><para>1</para></td>
+    <td><variablelist><title>Defined Terms:</title><varlistentry><term>CT</term><listitem><para/></listitem></varlistentry></variablelist></td>
+  </tr>
+</tbody></table>"#,
+        )
+        .unwrap();
+
+        let lists = value_lists(
+            &table,
+            "Patient Module Attributes",
+            "table_C.7-1",
+            &HashMap::new(),
+        );
+        assert_eq!(lists.len(), 2);
+        assert_eq!(lists[0].tag, "(0010,0040)");
+        assert_eq!(lists[0].kind, ValueListKind::EnumeratedValues);
+        assert_eq!(
+            lists[0].terms[1],
+            Term {
+                value: "F".to_owned(),
+                meaning: "female".to_owned()
+            }
+        );
+        assert_eq!(lists[1].kind, ValueListKind::DefinedTerms);
+        assert!(lists[1].contains("CT"));
+    }
+}
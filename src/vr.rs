@@ -0,0 +1,225 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The Value Representation of a data element as defined in DICOM part 5.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum VR {
+    AE,
+    AS,
+    AT,
+    CS,
+    DA,
+    DS,
+    DT,
+    FD,
+    FL,
+    IS,
+    LO,
+    LT,
+    OB,
+    OD,
+    OF,
+    OL,
+    OV,
+    OW,
+    PN,
+    SH,
+    SL,
+    SQ,
+    SS,
+    ST,
+    SV,
+    TM,
+    UC,
+    UI,
+    UL,
+    UN,
+    UR,
+    US,
+    UT,
+    UV,
+}
+
+impl VR {
+    /// All value representations in alphabetical order.
+    pub const ALL: [VR; 34] = [
+        VR::AE,
+        VR::AS,
+        VR::AT,
+        VR::CS,
+        VR::DA,
+        VR::DS,
+        VR::DT,
+        VR::FD,
+        VR::FL,
+        VR::IS,
+        VR::LO,
+        VR::LT,
+        VR::OB,
+        VR::OD,
+        VR::OF,
+        VR::OL,
+        VR::OV,
+        VR::OW,
+        VR::PN,
+        VR::SH,
+        VR::SL,
+        VR::SQ,
+        VR::SS,
+        VR::ST,
+        VR::SV,
+        VR::TM,
+        VR::UC,
+        VR::UI,
+        VR::UL,
+        VR::UN,
+        VR::UR,
+        VR::US,
+        VR::UT,
+        VR::UV,
+    ];
+
+    /// Returns the two upper-case letters of this VR (e.g. "TM").
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            VR::AE => "AE",
+            VR::AS => "AS",
+            VR::AT => "AT",
+            VR::CS => "CS",
+            VR::DA => "DA",
+            VR::DS => "DS",
+            VR::DT => "DT",
+            VR::FD => "FD",
+            VR::FL => "FL",
+            VR::IS => "IS",
+            VR::LO => "LO",
+            VR::LT => "LT",
+            VR::OB => "OB",
+            VR::OD => "OD",
+            VR::OF => "OF",
+            VR::OL => "OL",
+            VR::OV => "OV",
+            VR::OW => "OW",
+            VR::PN => "PN",
+            VR::SH => "SH",
+            VR::SL => "SL",
+            VR::SQ => "SQ",
+            VR::SS => "SS",
+            VR::ST => "ST",
+            VR::SV => "SV",
+            VR::TM => "TM",
+            VR::UC => "UC",
+            VR::UI => "UI",
+            VR::UL => "UL",
+            VR::UN => "UN",
+            VR::UR => "UR",
+            VR::US => "US",
+            VR::UT => "UT",
+            VR::UV => "UV",
+        }
+    }
+
+    /// Parses the VR column of the data dictionary, which either holds a
+    /// single VR (e.g. "TM"), multiple alternatives (e.g. "US or SS or OW")
+    /// or nothing at all (e.g. for "Item").
+    pub fn parse_list(s: &str) -> Result<Vec<VR>, Box<dyn Error>> {
+        if s.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        s.split(" or ").map(|vr| vr.parse()).collect()
+    }
+
+    /// Formats `vrs` the way the data dictionary does (e.g. "US or SS").
+    pub fn format_list(vrs: &[VR]) -> String {
+        vrs.iter().map(VR::as_str).collect::<Vec<_>>().join(" or ")
+    }
+}
+
+impl fmt::Display for VR {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for VR {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        match VR::ALL.iter().find(|vr| vr.as_str() == trimmed) {
+            Some(vr) => Ok(*vr),
+            None => Err(From::from(format!("Unknown VR '{}'", s))),
+        }
+    }
+}
+
+/// The unit the length of a value is measured in.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LengthUnit {
+    Bytes,
+    Characters,
+}
+
+/// The rules of a single VR as defined in the "DICOM Value Representations"
+/// table of part 5.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VrDefinition {
+    /// The VR (e.g. `VR::AE`).
+    pub vr: VR,
+
+    /// The name of the VR (e.g. "Application Entity").
+    pub name: String,
+
+    /// The definition of the VR as given in the standard.
+    pub definition: String,
+
+    /// The characters allowed in values of the VR (e.g. "Default Character
+    /// Repertoire and/or as defined by (0008,0005)."), "not applicable" for
+    /// binary VRs.
+    pub character_repertoire: String,
+
+    /// The "Length of Value" as given in the standard (e.g. "16 bytes
+    /// maximum").
+    pub length_of_value: String,
+
+    /// The maximum (or fixed) length of a single value, or `None` if only
+    /// limited by the length field (e.g. for "UT") or defined elsewhere (e.g.
+    /// for "OB").
+    pub max_length: Option<u32>,
+
+    /// The unit of `max_length`.
+    pub length_unit: LengthUnit,
+
+    /// Whether values always have exactly `max_length`.
+    pub fixed_length: bool,
+
+    /// The character used to pad values to an even length, or `None` if
+    /// values are not padded (e.g. for "SQ").
+    pub padding: Option<char>,
+
+    /// Whether the VR uses a 4 byte value length field (preceded by 2
+    /// reserved bytes) with explicit VR transfer syntaxes.
+    pub has_4_byte_length: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_list_handles_alternatives_and_empty_vr() {
+        assert_eq!(
+            VR::parse_list("US or SS or OW").unwrap(),
+            vec![VR::US, VR::SS, VR::OW]
+        );
+        assert!(VR::parse_list("").unwrap().is_empty());
+        assert!(VR::parse_list("XY").is_err());
+    }
+
+    #[test]
+    fn format_list_matches_dictionary_format() {
+        assert_eq!(VR::format_list(&[VR::OB, VR::OW]), "OB or OW");
+    }
+}